@@ -0,0 +1,74 @@
+//! Check the deck manifest shipped with the game against the `assets/` folder.
+//!
+//! Families and elements are described by `assets/deck.toml` and loaded at runtime
+//! (see `src/deck.rs`), so nothing is generated here: the build only fails, with the
+//! list of every family and file concerned, when the bundled manifest references
+//! files that do not exist.
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+#[path = "src/manifest.rs"]
+#[allow(dead_code)]
+mod manifest;
+
+fn main() {
+    println!("cargo:rerun-if-changed=assets");
+    println!("cargo:rerun-if-changed=src/manifest.rs");
+
+    let assets = Path::new("assets");
+    let text = std::fs::read_to_string(manifest::MANIFEST_PATH)
+        .unwrap_or_else(|err| panic!("cannot read {}: {err}", manifest::MANIFEST_PATH));
+    let manifest: manifest::Manifest = toml::from_str(&text)
+        .unwrap_or_else(|err| panic!("cannot parse {}: {err}", manifest::MANIFEST_PATH));
+
+    let mut missing = String::new();
+    let mut declared = BTreeSet::new();
+    for family in &manifest.families {
+        declared.insert(family.id.as_str());
+
+        let count = family.elements.len();
+        if !(manifest::MIN_ELEMENTS..=manifest::MAX_ELEMENTS).contains(&count) {
+            panic!(
+                "family '{}' has {count} elements, expected between {} and {}",
+                family.id,
+                manifest::MIN_ELEMENTS,
+                manifest::MAX_ELEMENTS
+            );
+        }
+
+        let folder = assets.join(&family.id);
+        let files = [family.sound.as_ref(), Some(&family.logo)]
+            .into_iter()
+            .flatten()
+            .chain(family.elements.iter().filter_map(|e| e.sound.as_ref()))
+            .chain(family.elements.iter().filter_map(|e| e.image.as_ref()));
+        for file in files {
+            let path = folder.join(file);
+            if !path.is_file() {
+                writeln!(missing, "  - family '{}': {}", family.id, path.display()).unwrap();
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        panic!(
+            "{} references missing files:\n{missing}",
+            manifest::MANIFEST_PATH
+        );
+    }
+
+    // Folders that are not in the manifest are not an error, they are simply not
+    // part of the game, but they are most likely an oversight.
+    for entry in std::fs::read_dir(assets).expect("cannot read assets/") {
+        let entry = entry.expect("cannot read assets/");
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.path().is_dir() && !declared.contains(name.as_ref()) {
+            println!(
+                "cargo:warning=assets/{name} is not declared in {}",
+                manifest::MANIFEST_PATH
+            );
+        }
+    }
+}
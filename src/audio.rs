@@ -1,43 +1,176 @@
 //! See [`Audio`].
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use gloo_timers::callback::Timeout;
 use wasm_bindgen::{closure::Closure, JsCast};
-use web_sys::HtmlAudioElement;
+use web_sys::{HtmlAudioElement, SpeechSynthesis, SpeechSynthesisUtterance};
 use yew::prelude::Event;
 
+/// Something to say, either recorded or synthesized.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sound {
+    /// Recorded sound file, absolute from the root of the website.
+    File(&'static str),
+    /// Text read by the browser's speech synthesis, in French.
+    ///
+    /// Used when a deck declares something without a recording.
+    Speech(String),
+}
+
 /// Audio element with a mandatory `onended` callback.
 ///
 /// Wrapper around an [`HtmlAudioElement`] that takes care of keeping
 /// the callback alive.
+///
+/// [Sounds][Sound] without a recording are read by the browser's speech synthesis
+/// instead, calling the same `onended` callback once done, or if it failed.
 pub struct Audio {
     /// Inner element, created once only.
     inner: HtmlAudioElement,
+    /// Text being read by the speech synthesis, if any.
+    ///
+    /// Kept around so that it can be silenced without triggering the callback (and
+    /// because some browsers forget to call it if the utterance is garbage collected).
+    utterance: RefCell<Option<SpeechSynthesisUtterance>>,
+    /// Calls `onended` right away when the speech synthesis is not available, so
+    /// that the caller is not stuck waiting for a sound that never comes.
+    fallback: RefCell<Option<Timeout>>,
+    /// The `onended` callback, for [`Self::fallback`].
+    onended: Rc<dyn Fn(Event)>,
     /// Handle to keep the `onended` closure alive for later use.
-    _onended_listener: Closure<dyn Fn(Event)>,
+    onended_listener: Closure<dyn Fn(Event)>,
 }
 
 impl Audio {
     /// Creates a new `Audio` with the given callback for the `onended` event.
     pub fn new(onended: impl Fn(Event) + 'static) -> Self {
         let inner = HtmlAudioElement::new().unwrap();
-        let onended_listener = Closure::<dyn Fn(Event)>::wrap(Box::new(onended));
+        let onended: Rc<dyn Fn(Event)> = Rc::new(onended);
+        let listener = Rc::clone(&onended);
+        let onended_listener = Closure::<dyn Fn(Event)>::wrap(Box::new(move |e| listener(e)));
         inner.set_onended(Some(onended_listener.as_ref().unchecked_ref()));
         Self {
             inner,
-            _onended_listener: onended_listener,
+            utterance: RefCell::new(None),
+            fallback: RefCell::new(None),
+            onended,
+            onended_listener,
         }
     }
 
-    /// Play the audio. A source must have been set with [`Self::set_src()`] before.
-    pub fn play(&self) {
-        self.inner.play().ok();
+    /// Play the given sound from its start.
+    pub fn play(&self, sound: &Sound) {
+        match sound {
+            Sound::File(src) => {
+                self.inner.set_src(src);
+                self.inner.play().ok();
+            }
+            Sound::Speech(text) => self.speak(text),
+        }
     }
 
     /// Pause the audio.
+    ///
+    /// Speech synthesis cannot be resumed reliably so it is stopped instead.
     pub fn pause(&self) {
         self.inner.pause().ok();
+        self.stop_speech();
     }
 
-    /// Set the source to play.
-    pub fn set_src(&self, src: &str) {
-        self.inner.set_src(src)
+    /// Read `text` in French with the speech synthesis of the browser.
+    ///
+    /// What was being read is stopped first, else both would call `onended`.
+    fn speak(&self, text: &str) {
+        self.stop_speech();
+
+        let synthesis = speech_synthesis();
+        let utterance = SpeechSynthesisUtterance::new_with_text(text).ok();
+        let (synthesis, utterance) = match synthesis.zip(utterance) {
+            Some(speech) => speech,
+            None => {
+                let onended = Rc::clone(&self.onended);
+                let timeout = Timeout::new(0, move || {
+                    if let Ok(event) = Event::new("ended") {
+                        onended(event);
+                    }
+                });
+                *self.fallback.borrow_mut() = Some(timeout);
+                return;
+            }
+        };
+
+        let listener = Some(self.onended_listener.as_ref().unchecked_ref());
+        utterance.set_lang("fr-FR");
+        // Browsers fire `error` instead of `end` when the synthesis fails.
+        utterance.set_onend(listener);
+        utterance.set_onerror(listener);
+        synthesis.speak(&utterance);
+        *self.utterance.borrow_mut() = Some(utterance);
     }
+
+    /// Silence the speech synthesis without calling `onended`.
+    fn stop_speech(&self) {
+        self.fallback.borrow_mut().take().map(Timeout::cancel);
+
+        if let Some(utterance) = self.utterance.borrow_mut().take() {
+            utterance.set_onend(None);
+            utterance.set_onerror(None);
+            if let Some(synthesis) = speech_synthesis() {
+                synthesis.cancel();
+            }
+        }
+    }
+}
+
+/// Several sounds played one after the other with a single [`Audio`].
+///
+/// Used to say a whole sentence ("Dans la famille ...", then "je voudrais ...")
+/// outside of the [caller][crate::game::Game], which sequences the sounds itself.
+pub struct Playlist {
+    /// Audio element, created once only (see [`crate::game::Game`] for why).
+    audio: Audio,
+    /// Sounds left to play.
+    queue: VecDeque<Sound>,
+}
+
+impl Playlist {
+    /// Creates a new `Playlist`, `onended` is called at the end of *each* sound and
+    /// must lead to a call to [`Self::play_next()`].
+    pub fn new(onended: impl Fn(Event) + 'static) -> Self {
+        Self {
+            audio: Audio::new(onended),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Stop what was playing and play `sounds` in order.
+    pub fn play(&mut self, sounds: impl IntoIterator<Item = Sound>) {
+        self.stop();
+        self.queue.extend(sounds);
+        self.play_next();
+    }
+
+    /// Play the next sound, returns `false` if there was none left.
+    pub fn play_next(&mut self) -> bool {
+        match self.queue.pop_front() {
+            Some(sound) => {
+                self.audio.play(&sound);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop playing and forget the sounds left.
+    pub fn stop(&mut self) {
+        self.queue.clear();
+        self.audio.pause();
+    }
+}
+
+/// Speech synthesis of the browser, if it has one.
+fn speech_synthesis() -> Option<SpeechSynthesis> {
+    web_sys::window()?.speech_synthesis().ok()
 }
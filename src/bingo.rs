@@ -0,0 +1,310 @@
+//! Bingo grids filled with elements, for the caller used as a bingo draw.
+//!
+//! See [`generate()`] and [`Grid`].
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::history::History;
+use crate::sentences::Sentence;
+
+/// Smallest side of a grid, in cells.
+pub const MIN_SIZE: usize = 2;
+/// Largest side of a grid, in cells.
+pub const MAX_SIZE: usize = 5;
+/// Maximum number of grids generated at once.
+pub const MAX_GRIDS: usize = 60;
+/// Random tries to find a new grid, per grid, before giving up.
+const TRIES_PER_GRID: usize = 100;
+
+/// How to generate grids, the same settings and selection always give the same
+/// grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BingoSettings {
+    /// Seed of the random generation.
+    pub seed: u64,
+    /// Number of grids.
+    pub grids: usize,
+    /// Side of each grid, in cells.
+    pub size: usize,
+}
+
+/// A bingo grid ("carton"), its cells are distinct elements.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grid {
+    /// Number written on the grid, starting at 1, to check a "Bingo !" claim.
+    pub number: usize,
+    /// Side of the grid, in cells.
+    size: usize,
+    /// Cells, row by row.
+    cells: Vec<Sentence>,
+}
+
+/// Why grids could not be generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BingoError {
+    /// Fewer elements selected than cells in a grid.
+    NotEnoughElements { selected: usize, cells: usize },
+    /// Not enough different grids could be found, select more elements or make
+    /// fewer or smaller grids.
+    NotEnoughGrids { found: usize },
+}
+
+impl std::fmt::Display for BingoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEnoughElements { selected, cells } => write!(
+                f,
+                "{selected} éléments choisis pour des grilles de {cells} cases, choisissez plus d'éléments ou des grilles plus petites"
+            ),
+            Self::NotEnoughGrids { found } => write!(
+                f,
+                "seulement {found} grilles différentes trouvées, choisissez plus d'éléments ou moins de grilles"
+            ),
+        }
+    }
+}
+
+/// Generate `settings.grids` different grids from `selection`.
+///
+/// Reproducible: the same selection and settings always give the same grids, so
+/// that printed grids can be checked later.
+pub fn generate(selection: &[Sentence], settings: BingoSettings) -> Result<Vec<Grid>, BingoError> {
+    let cells = settings.size * settings.size;
+    if selection.len() < cells {
+        return Err(BingoError::NotEnoughElements {
+            selected: selection.len(),
+            cells,
+        });
+    }
+
+    let mut pool = selection.to_vec();
+    // Only the seed decides the grids, not the order of the selection.
+    pool.sort_unstable();
+    let mut rng = ChaCha8Rng::seed_from_u64(settings.seed);
+
+    let mut seen = HashSet::new();
+    let mut grids = Vec::with_capacity(settings.grids);
+    for _ in 0..settings.grids * TRIES_PER_GRID {
+        if grids.len() == settings.grids {
+            break;
+        }
+
+        pool.shuffle(&mut rng);
+        let grid = pool[..cells].to_vec();
+        let mut key = grid.clone();
+        key.sort_unstable();
+        if seen.insert(key) {
+            grids.push(Grid {
+                number: grids.len() + 1,
+                size: settings.size,
+                cells: grid,
+            });
+        }
+    }
+
+    if grids.len() < settings.grids {
+        return Err(BingoError::NotEnoughGrids { found: grids.len() });
+    }
+    Ok(grids)
+}
+
+impl Grid {
+    /// Number of complete lines: rows, columns and both diagonals whose elements
+    /// have all been `called`.
+    pub fn lines(&self, called: &HashSet<Sentence>) -> usize {
+        let n = self.size;
+        let mut lines: Vec<Vec<usize>> = Vec::with_capacity(2 * n + 2);
+        lines.extend((0..n).map(|r| (0..n).map(|c| r * n + c).collect()));
+        lines.extend((0..n).map(|c| (0..n).map(|r| r * n + c).collect()));
+        lines.push((0..n).map(|i| i * n + i).collect());
+        lines.push((0..n).map(|i| i * n + n - 1 - i).collect());
+
+        lines
+            .iter()
+            .filter(|line| line.iter().all(|&i| called.contains(&self.cells[i])))
+            .count()
+    }
+
+    /// `true` if all the elements of the grid have been `called` ("carton plein").
+    pub fn is_full(&self, called: &HashSet<Sentence>) -> bool {
+        self.cells.iter().all(|st| called.contains(st))
+    }
+
+    /// The grid as a table, to be printed.
+    pub fn render(&self) -> Html {
+        html! {
+            <div class="bingo_grid">
+                <h3> { format!("Grille n°{}", self.number) } </h3>
+                <table>
+                    { for self.cells.chunks(self.size).map(|row| html! {
+                        <tr>
+                            { for row.iter().map(|st| html! {
+                                <td style={ format!("border-color:{};", st.family().color()) }>
+                                    { st.label() }
+                                </td>
+                            }) }
+                        </tr>
+                    }) }
+                </table>
+            </div>
+        }
+    }
+}
+
+/// Grids that could have won with the sentences drawn so far, to check a
+/// "Bingo !" claim. Empty if there are no grids.
+pub fn render_check(grids: &[Grid], history: &History) -> Html {
+    if grids.is_empty() {
+        return html! {};
+    }
+
+    let called: HashSet<_> = history.sentences().collect();
+    let full: Vec<_> = grids
+        .iter()
+        .filter(|grid| grid.is_full(&called))
+        .map(|grid| grid.number.to_string())
+        .collect();
+    let lines: Vec<_> = grids
+        .iter()
+        .map(|grid| (grid.number, grid.lines(&called)))
+        .filter(|&(_, lines)| lines > 0)
+        .collect();
+
+    html! {
+        <details open=true>
+            <summary> { "Vérifier un bingo" } </summary>
+            if full.is_empty() {
+                <p> { "Aucun carton plein." } </p>
+            } else {
+                <p> { format!("Carton plein : grille(s) {}", full.join(", ")) } </p>
+            }
+            if lines.is_empty() {
+                <p> { "Aucune ligne complète." } </p>
+            } else {
+                <ul>
+                    { for lines.into_iter().map(|(number, lines)| html! {
+                        <li> { format!("Grille n°{number} : {lines} ligne(s) complète(s)") } </li>
+                    }) }
+                </ul>
+            }
+        </details>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::test_deck::{card, family};
+
+    /// Families 0 and 1, 12 elements.
+    fn selection() -> Vec<Sentence> {
+        [0, 1]
+            .into_iter()
+            .flat_map(|f| Sentence::all_in(family(f)))
+            .collect()
+    }
+
+    fn settings(seed: u64) -> BingoSettings {
+        BingoSettings {
+            seed,
+            grids: 30,
+            size: 3,
+        }
+    }
+
+    #[test]
+    fn same_seed_same_grids() {
+        let selection = selection();
+        let grids = generate(&selection, settings(42)).unwrap();
+        assert_eq!(grids, generate(&selection, settings(42)).unwrap());
+
+        // The order of the selection does not matter.
+        let reversed: Vec<_> = selection.iter().rev().copied().collect();
+        assert_eq!(grids, generate(&reversed, settings(42)).unwrap());
+
+        assert_ne!(grids, generate(&selection, settings(43)).unwrap());
+    }
+
+    #[test]
+    fn grids_are_distinct() {
+        let grids = generate(&selection(), settings(7)).unwrap();
+        assert_eq!(grids.len(), 30);
+
+        let mut seen = HashSet::new();
+        for (i, grid) in grids.iter().enumerate() {
+            assert_eq!(grid.number, i + 1);
+            assert_eq!(grid.cells.len(), 9);
+            let cells: HashSet<_> = grid.cells.iter().collect();
+            assert_eq!(cells.len(), 9, "repeated element in grid {}", grid.number);
+
+            let mut key = grid.cells.clone();
+            key.sort_unstable();
+            assert!(seen.insert(key), "grid {} printed twice", grid.number);
+        }
+    }
+
+    #[test]
+    fn generation_errors() {
+        let few: Vec<_> = Sentence::all_in(family(6)).collect();
+        assert_eq!(
+            generate(&few, settings(0)),
+            Err(BingoError::NotEnoughElements {
+                selected: 3,
+                cells: 9
+            })
+        );
+
+        // 4 elements make a single 2 × 2 grid.
+        let four: Vec<_> = (0..4).map(|e| card(0, e)).collect();
+        let settings = BingoSettings {
+            seed: 0,
+            grids: 3,
+            size: 2,
+        };
+        assert_eq!(
+            generate(&four, settings),
+            Err(BingoError::NotEnoughGrids { found: 1 })
+        );
+    }
+
+    #[test]
+    fn lines_and_full_card() {
+        // 0 1 2
+        // 3 4 5
+        // 6 7 8
+        let cells: Vec<_> = (0..6)
+            .map(|e| card(0, e))
+            .chain((0..3).map(|e| card(1, e)))
+            .collect();
+        let grid = Grid {
+            number: 1,
+            size: 3,
+            cells,
+        };
+
+        // Sentences called in order, as in the history of the caller, starting
+        // with an element from outside the grid that changes nothing.
+        let mut called = HashSet::from([card(2, 0)]);
+        let mut call = |cells: &[usize]| {
+            called.extend(cells.iter().map(|&i| grid.cells[i]));
+            (grid.lines(&called), grid.is_full(&called))
+        };
+
+        assert_eq!(call(&[]), (0, false));
+        assert_eq!(call(&[0, 1]), (0, false));
+        // First row.
+        assert_eq!(call(&[2]), (1, false));
+        // First column.
+        assert_eq!(call(&[3, 6]), (2, false));
+        // With the last corner, the center completes both diagonals.
+        assert_eq!(call(&[8]), (2, false));
+        assert_eq!(call(&[4]), (4, false));
+        // Everything: 3 rows, 3 columns and 2 diagonals.
+        assert_eq!(call(&[5, 7]), (8, true));
+    }
+}
@@ -0,0 +1,158 @@
+//! Printing bingo grids before a bingo call.
+//!
+//! See [`BingoCards`].
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::bingo::{self, BingoError, BingoSettings, Grid, MAX_GRIDS, MAX_SIZE, MIN_SIZE};
+use crate::sentences::{Sentence, Sentences};
+use crate::style;
+
+/// Component generating the bingo grids for the selection, to print them then
+/// launch the caller as the bingo draw.
+pub struct BingoCards {
+    /// How the grids are generated.
+    settings: BingoSettings,
+    /// Grids for the current settings, or why there are none.
+    grids: Result<Vec<Grid>, BingoError>,
+}
+
+#[derive(Debug)]
+pub enum BingoCardsMsg {
+    /// Generate the grids again with another seed.
+    SetSeed(u64),
+    /// Generate another number of grids.
+    SetGrids(usize),
+    /// Generate grids of another size.
+    SetSize(usize),
+    /// Open the print dialog of the browser.
+    Print,
+    /// Launch the caller with the grids.
+    Call,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct BingoCardsProps {
+    /// Elements to fill the grids with.
+    pub selection: Vec<Sentence>,
+    /// Called to launch the caller with the grids, to check the claims.
+    pub on_call: Callback<Vec<Grid>>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for BingoCards {
+    type Message = BingoCardsMsg;
+    type Properties = BingoCardsProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let selection = &ctx.props().selection;
+        // The largest grids possible, up to 3 by 3.
+        let size = (MIN_SIZE..=3)
+            .rev()
+            .find(|size| size * size <= selection.len())
+            .unwrap_or(MIN_SIZE);
+        let settings = BingoSettings {
+            seed: Sentences::random_seed(),
+            grids: 10,
+            size,
+        };
+
+        Self {
+            settings,
+            grids: bingo::generate(selection, settings),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            BingoCardsMsg::SetSeed(seed) => self.settings.seed = seed,
+            BingoCardsMsg::SetGrids(grids) => self.settings.grids = grids.clamp(1, MAX_GRIDS),
+            BingoCardsMsg::SetSize(size) => self.settings.size = size.clamp(MIN_SIZE, MAX_SIZE),
+            BingoCardsMsg::Print => {
+                if let Some(window) = web_sys::window() {
+                    window.print().ok();
+                }
+                return false;
+            }
+            BingoCardsMsg::Call => {
+                if let Ok(grids) = &self.grids {
+                    ctx.props().on_call.emit(grids.clone());
+                }
+                return false;
+            }
+            BingoCardsMsg::Exit => {
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+        }
+
+        self.grids = bingo::generate(&ctx.props().selection, self.settings);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        html! {
+            <>
+                <div class="no_print">
+                    <p> { "Taille des grilles" } </p>
+                    { for (MIN_SIZE..=MAX_SIZE).map(|size| html! {
+                        <button
+                            onclick={ link.callback(move |_| BingoCardsMsg::SetSize(size)) }
+                            class={ style::button_select_family(size == self.settings.size) }
+                        >
+                            { format!("{size} × {size}") }
+                        </button>
+                    }) }
+                    <label for="grids"> { "Nombre de grilles" } </label>
+                    <input
+                        id="grids"
+                        type="number"
+                        min="1"
+                        max={ MAX_GRIDS.to_string() }
+                        value={ self.settings.grids.to_string() }
+                        onchange={ link.batch_callback(|e: Event| {
+                            // Unchecked: we define the callback inside the element it concerns, we cannot
+                            // be referencing the wrong one.
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            input.value().parse().ok().map(BingoCardsMsg::SetGrids)
+                        }) }
+                    />
+                    <label for="bingo_seed"> { "Graine (les mêmes éléments et la même graine donnent les mêmes grilles)" } </label>
+                    <input
+                        id="bingo_seed"
+                        type="number"
+                        min="0"
+                        value={ self.settings.seed.to_string() }
+                        onchange={ link.batch_callback(|e: Event| {
+                            // Unchecked: we define the callback inside the element it concerns, we cannot
+                            // be referencing the wrong one.
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            input.value().parse().ok().map(BingoCardsMsg::SetSeed)
+                        }) }
+                    />
+                    <hr />
+                    if self.grids.is_ok() {
+                        <button onclick={ link.callback(|_| BingoCardsMsg::Print) }> { "Imprimer les grilles" } </button>
+                        <button onclick={ link.callback(|_| BingoCardsMsg::Call) }> { "Lancer l'appel avec ces grilles" } </button>
+                    }
+                    <button onclick={ link.callback(|_| BingoCardsMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+                    <hr />
+                </div>
+                { match &self.grids {
+                    Ok(grids) => html! {
+                        <>
+                            <p> { format!("Graine {}", self.settings.seed) } </p>
+                            { for grids.iter().map(Grid::render) }
+                        </>
+                    },
+                    Err(err) => html! { <p> { format!("Impossible de générer les grilles : {err}") } </p> },
+                } }
+            </>
+        }
+    }
+}
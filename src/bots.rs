@@ -0,0 +1,177 @@
+//! Computer opponents for the [card game][crate::card_game].
+//!
+//! See [`Bot`].
+use std::collections::{HashMap, HashSet};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::card_game::{Ask, AskOutcome, AskResult, CardGame};
+use crate::family::Family;
+use crate::sentences::Sentence;
+
+/// How a bot chooses what to ask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotStrategy {
+    /// Any card it can ask for, to any player.
+    Random,
+    /// Remembers who asked for what and who got which card, and uses it.
+    Memory,
+    /// Works on the family it is closest to complete, using its memory too.
+    NearComplete,
+}
+
+impl BotStrategy {
+    /// All the strategies, from the easiest to the hardest.
+    pub const ALL: [Self; 3] = [Self::Random, Self::Memory, Self::NearComplete];
+
+    /// Description shown to players.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Random => "Au hasard",
+            Self::Memory => "Avec de la mémoire",
+            Self::NearComplete => "Vise les familles presque complètes",
+        }
+    }
+}
+
+/// A computer player.
+///
+/// Every ask of the game, its own included, must be given to [`Bot::observe()`]
+/// so that it can remember it.
+#[derive(Debug, Clone)]
+pub struct Bot {
+    /// Player of the bot in the game.
+    player: usize,
+    /// How the bot chooses what to ask.
+    strategy: BotStrategy,
+    /// Cards known to be held by a player.
+    cards: HashMap<Sentence, usize>,
+    /// Players known to hold at least one card of a family, because they asked for it.
+    families: HashMap<Family, HashSet<usize>>,
+    /// Cards players did not have when asked, until they draw.
+    lacks: HashSet<(usize, Sentence)>,
+}
+
+impl Bot {
+    /// A new bot for `player`, which knows nothing yet.
+    pub fn new(player: usize, strategy: BotStrategy) -> Self {
+        Self {
+            player,
+            strategy,
+            cards: HashMap::new(),
+            families: HashMap::new(),
+            lacks: HashSet::new(),
+        }
+    }
+
+    /// Player of the bot in the game.
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// Remember what an ask revealed.
+    pub fn observe(&mut self, ask: Ask, result: AskResult) {
+        let family = ask.card.family();
+
+        // Asking for a card means holding its family.
+        self.families.entry(family).or_default().insert(ask.asker);
+        match result.outcome {
+            AskOutcome::Given | AskOutcome::LuckyDraw => {
+                self.cards.insert(ask.card, ask.asker);
+                self.lacks.remove(&(ask.asker, ask.card));
+            }
+            AskOutcome::Drew(_) | AskOutcome::EmptyPile => {
+                if self.cards.get(&ask.card) == Some(&ask.target) {
+                    self.cards.remove(&ask.card);
+                }
+                self.lacks.insert((ask.target, ask.card));
+            }
+        }
+        if let AskOutcome::Drew(_) = result.outcome {
+            // The drawn card is hidden from the other players, anything may
+            // have changed for the asker.
+            self.lacks.retain(|&(player, _)| player != ask.asker);
+        }
+
+        if let Some(done) = result.completed {
+            self.cards.retain(|card, _| card.family() != done);
+            self.families.remove(&done);
+            self.lacks.retain(|(_, card)| card.family() != done);
+        }
+    }
+
+    /// Choose what to ask, `None` if it is not the turn of the bot.
+    pub fn choose(&self, game: &CardGame, rng: &mut impl Rng) -> Option<Ask> {
+        if game.is_over() || game.current_player() != self.player {
+            return None;
+        }
+
+        let mut cards = game.askable_cards(self.player);
+        if self.strategy == BotStrategy::NearComplete {
+            // Keep only the family with the most cards in hand, ties are left to chance.
+            let hand = game.hand(self.player);
+            let held = |f: Family| hand.iter().filter(|c| c.family() == f).count();
+            let best = cards.iter().map(|c| held(c.family())).max()?;
+            cards.retain(|c| held(c.family()) == best);
+        }
+
+        if self.strategy != BotStrategy::Random {
+            // A card known to be in the hand of someone else.
+            let known: Vec<_> = cards
+                .iter()
+                .filter_map(|&card| {
+                    let target = *self.cards.get(&card)?;
+                    (target != self.player).then_some(Ask {
+                        asker: self.player,
+                        target,
+                        card,
+                    })
+                })
+                .collect();
+            if let Some(&ask) = known.choose(rng) {
+                return Some(ask);
+            }
+
+            // A family known to be in the hand of someone else.
+            let likely: Vec<_> = cards
+                .iter()
+                .flat_map(|&card| {
+                    self.families
+                        .get(&card.family())
+                        .into_iter()
+                        .flatten()
+                        .filter(move |&&target| {
+                            target != self.player
+                                && !game.hand(target).is_empty()
+                                && !self.lacks.contains(&(target, card))
+                        })
+                        .map(move |&target| Ask {
+                            asker: self.player,
+                            target,
+                            card,
+                        })
+                })
+                .collect();
+            if let Some(&ask) = likely.choose(rng) {
+                return Some(ask);
+            }
+        }
+
+        let card = *cards.choose(rng)?;
+        let others: Vec<_> = (0..game.players()).filter(|&p| p != self.player).collect();
+        // Players without cards cannot give anything, avoid them if possible.
+        let with_cards: Vec<_> = others
+            .iter()
+            .copied()
+            .filter(|&p| !game.hand(p).is_empty())
+            .collect();
+        let target = *with_cards.choose(rng).or_else(|| others.choose(rng))?;
+
+        Some(Ask {
+            asker: self.player,
+            target,
+            card,
+        })
+    }
+}
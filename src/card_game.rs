@@ -0,0 +1,569 @@
+//! Rules of the seven families card game, independent of any display.
+//!
+//! See [`CardGame`].
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::family::Family;
+use crate::sentences::Sentence;
+
+/// Minimum number of players in a game.
+pub const MIN_PLAYERS: usize = 2;
+/// Maximum number of players in a game.
+pub const MAX_PLAYERS: usize = 6;
+
+/// A game of seven families ("jeu des 7 familles").
+///
+/// Each player is dealt a hand, the rest of the cards form the pile ("pioche").
+/// In turn, players ask another player for a card of a family they already hold:
+///
+/// - If the other player has it, they give it and the asker plays again.
+/// - Otherwise, the asker draws from the pile: if it is the card they asked for
+///   ("bonne pioche"), they play again, else the turn passes to the next player.
+///
+/// A player holding every card of a family puts it down. The game ends once all
+/// families are down, the winners are those with the most families.
+#[derive(Debug, Clone)]
+pub struct CardGame {
+    /// Cards in the hand of each player.
+    hands: Vec<Vec<Sentence>>,
+    /// Families put down by each player.
+    completed: Vec<Vec<Family>>,
+    /// Cards left to draw, the next one is last.
+    pile: Vec<Sentence>,
+    /// Player whose turn it is.
+    current: usize,
+}
+
+/// Why a game could not be set up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupError {
+    /// Not between [`MIN_PLAYERS`] and [`MAX_PLAYERS`] players.
+    PlayerCount(usize),
+    /// Not enough cards to give at least one to each player.
+    NotEnoughCards,
+}
+
+/// A question from the current player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ask {
+    /// Player asking.
+    pub asker: usize,
+    /// Player asked.
+    pub target: usize,
+    /// Card asked for.
+    pub card: Sentence,
+}
+
+/// What happened after an [`Ask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AskResult {
+    /// How the card was (or was not) obtained.
+    pub outcome: AskOutcome,
+    /// Family the asker completed and put down with the card they got, if any.
+    pub completed: Option<Family>,
+}
+
+/// How an [`Ask`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AskOutcome {
+    /// The target gave the card, the asker plays again.
+    Given,
+    /// The target did not have it but the asker drew it from the pile
+    /// ("bonne pioche"), they play again.
+    LuckyDraw,
+    /// The target did not have it and the asker drew another card,
+    /// the turn passes.
+    Drew(Sentence),
+    /// The target did not have it and the pile is empty, the turn passes.
+    EmptyPile,
+}
+
+/// Why an [`Ask`] is not allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AskError {
+    /// The game is over.
+    GameOver,
+    /// It is not the turn of the asker.
+    NotYourTurn,
+    /// The target is the asker or is not a player of this game.
+    InvalidTarget,
+    /// The asker holds no card of the family of the card.
+    FamilyNotHeld,
+    /// The asker already holds the card.
+    AlreadyHeld,
+}
+
+impl CardGame {
+    /// Deal the cards of `families` to `players` players.
+    ///
+    /// Each player gets 7 cards with 2 or 3 players and 6 with more, less if there
+    /// are not enough cards. The first player starts.
+    pub fn new(
+        families: &HashSet<Family>,
+        players: usize,
+        rng: &mut impl Rng,
+    ) -> Result<Self, SetupError> {
+        if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+            return Err(SetupError::PlayerCount(players));
+        }
+
+        let mut pile: Vec<_> = families.iter().flat_map(|&f| Sentence::all_in(f)).collect();
+        if pile.len() < players {
+            return Err(SetupError::NotEnoughCards);
+        }
+        // The iteration order of a `HashSet` is random, sort first so that only
+        // `rng` decides the deal.
+        pile.sort_unstable();
+        pile.shuffle(rng);
+
+        let hand_size = if players <= 3 { 7 } else { 6 };
+        let hand_size = hand_size.min(pile.len() / players);
+        let hands = (0..players)
+            .map(|_| {
+                let mut hand = pile.split_off(pile.len() - hand_size);
+                hand.sort_unstable();
+                hand
+            })
+            .collect();
+
+        let mut game = Self {
+            hands,
+            completed: vec![Vec::new(); players],
+            pile,
+            current: 0,
+        };
+        // A hand may already hold a whole family with small decks.
+        for player in 0..players {
+            while let Some(family) = game.complete_family(player) {
+                game.completed[player].push(family);
+            }
+        }
+        game.ensure_can_play();
+
+        Ok(game)
+    }
+
+    /// Number of players.
+    pub fn players(&self) -> usize {
+        self.hands.len()
+    }
+
+    /// Player whose turn it is.
+    pub fn current_player(&self) -> usize {
+        self.current
+    }
+
+    /// Cards in the hand of `player`, sorted by family.
+    pub fn hand(&self, player: usize) -> &[Sentence] {
+        &self.hands[player]
+    }
+
+    /// Families put down by `player`.
+    pub fn completed(&self, player: usize) -> &[Family] {
+        &self.completed[player]
+    }
+
+    /// Number of cards left in the pile.
+    pub fn pile_len(&self) -> usize {
+        self.pile.len()
+    }
+
+    /// Families of which `player` holds at least one card, the only ones
+    /// they can ask for.
+    pub fn askable_families(&self, player: usize) -> Vec<Family> {
+        let mut families: Vec<_> = self.hands[player].iter().map(|c| c.family()).collect();
+        families.dedup();
+        families
+    }
+
+    /// Cards `player` can ask for: those missing from the families they hold.
+    pub fn askable_cards(&self, player: usize) -> Vec<Sentence> {
+        self.askable_families(player)
+            .into_iter()
+            .flat_map(Sentence::all_in)
+            .filter(|card| !self.hands[player].contains(card))
+            .collect()
+    }
+
+    /// `true` once every family has been put down.
+    pub fn is_over(&self) -> bool {
+        self.pile.is_empty() && self.hands.iter().all(Vec::is_empty)
+    }
+
+    /// Number of families put down by each player.
+    pub fn scores(&self) -> Vec<usize> {
+        self.completed.iter().map(Vec::len).collect()
+    }
+
+    /// Players with the most families, several in case of a tie.
+    pub fn winners(&self) -> Vec<usize> {
+        let scores = self.scores();
+        let best = scores.iter().copied().max().unwrap_or(0);
+        (0..scores.len()).filter(|&p| scores[p] == best).collect()
+    }
+
+    /// Ask a card from another player, following the rules described in [`CardGame`].
+    pub fn ask(&mut self, ask: Ask) -> Result<AskResult, AskError> {
+        let Ask {
+            asker,
+            target,
+            card,
+        } = ask;
+
+        if self.is_over() {
+            return Err(AskError::GameOver);
+        }
+        if asker != self.current {
+            return Err(AskError::NotYourTurn);
+        }
+        if target == asker || target >= self.players() {
+            return Err(AskError::InvalidTarget);
+        }
+        if self.hands[asker].contains(&card) {
+            return Err(AskError::AlreadyHeld);
+        }
+        if !self.hands[asker]
+            .iter()
+            .any(|c| c.family() == card.family())
+        {
+            return Err(AskError::FamilyNotHeld);
+        }
+
+        let outcome = if let Some(pos) = self.hands[target].iter().position(|&c| c == card) {
+            self.hands[target].remove(pos);
+            self.give(asker, card);
+            AskOutcome::Given
+        } else {
+            match self.pile.pop() {
+                Some(drawn) if drawn == card => {
+                    self.give(asker, drawn);
+                    AskOutcome::LuckyDraw
+                }
+                Some(drawn) => {
+                    self.give(asker, drawn);
+                    AskOutcome::Drew(drawn)
+                }
+                None => AskOutcome::EmptyPile,
+            }
+        };
+
+        let completed = self.complete_family(asker);
+        if let Some(family) = completed {
+            self.completed[asker].push(family);
+        }
+        if matches!(outcome, AskOutcome::Drew(_) | AskOutcome::EmptyPile) {
+            self.current = (self.current + 1) % self.players();
+        }
+        self.ensure_can_play();
+
+        Ok(AskResult { outcome, completed })
+    }
+
+    /// Put `card` in the hand of `player`, keeping it sorted.
+    fn give(&mut self, player: usize, card: Sentence) {
+        let hand = &mut self.hands[player];
+        let pos = hand.binary_search(&card).unwrap_or_else(|pos| pos);
+        hand.insert(pos, card);
+    }
+
+    /// If `player` holds a whole family, remove it from their hand and return it.
+    fn complete_family(&mut self, player: usize) -> Option<Family> {
+        let hand = &mut self.hands[player];
+        let family = hand
+            .iter()
+            .map(|c| c.family())
+            .find(|&f| Sentence::all_in(f).all(|card| hand.contains(&card)))?;
+        hand.retain(|c| c.family() != family);
+        Some(family)
+    }
+
+    /// Make sure the current player can ask for something.
+    ///
+    /// A player without cards draws one from the pile, or passes their turn if it
+    /// is empty.
+    fn ensure_can_play(&mut self) {
+        while !self.is_over() && self.hands[self.current].is_empty() {
+            match self.pile.pop() {
+                Some(card) => {
+                    let player = self.current;
+                    self.give(player, card);
+                    if let Some(family) = self.complete_family(player) {
+                        self.completed[player].push(family);
+                    }
+                }
+                None => self.current = (self.current + 1) % self.players(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::deck::test_deck::{card, family, install_deck};
+
+    fn families(families: &[usize]) -> HashSet<Family> {
+        families.iter().map(|&f| family(f)).collect()
+    }
+
+    /// A game in a given position, hands must be sorted.
+    fn game(hands: Vec<Vec<Sentence>>, pile: Vec<Sentence>, current: usize) -> CardGame {
+        CardGame {
+            completed: vec![Vec::new(); hands.len()],
+            hands,
+            pile,
+            current,
+        }
+    }
+
+    fn deal(families: &HashSet<Family>, players: usize, seed: u64) -> Result<CardGame, SetupError> {
+        CardGame::new(families, players, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Every card of the game, in hands, put down or in the pile.
+    fn card_count(game: &CardGame) -> usize {
+        let hands: usize = game.hands.iter().map(Vec::len).sum();
+        let completed: usize = game
+            .completed
+            .iter()
+            .flatten()
+            .map(|&f| Sentence::all_in(f).count())
+            .sum();
+        hands + completed + game.pile.len()
+    }
+
+    #[test]
+    fn setup_errors() {
+        install_deck();
+        let all = families(&[0, 1, 2]);
+
+        assert_eq!(deal(&all, 1, 0).unwrap_err(), SetupError::PlayerCount(1));
+        assert_eq!(deal(&all, 7, 0).unwrap_err(), SetupError::PlayerCount(7));
+        assert_eq!(
+            deal(&HashSet::new(), 2, 0).unwrap_err(),
+            SetupError::NotEnoughCards
+        );
+        assert_eq!(
+            deal(&families(&[6]), 4, 0).unwrap_err(),
+            SetupError::NotEnoughCards
+        );
+    }
+
+    #[test]
+    fn hand_sizes() {
+        install_deck();
+        let all = families(&[0, 1, 2, 3, 4, 5, 6]);
+
+        for (players, size) in [(2, 7), (3, 7), (4, 6), (5, 6), (6, 6)] {
+            let game = deal(&all, players, 1).unwrap();
+            assert_eq!(game.pile_len(), 39 - players * size);
+            for player in 0..players {
+                let put_down = game.completed(player).len() * 6;
+                assert_eq!(game.hand(player).len() + put_down, size);
+            }
+        }
+
+        // 6 cards for 4 players: only one card each.
+        let game = deal(&families(&[0]), 4, 1).unwrap();
+        assert!((0..4).all(|player| game.hand(player).len() == 1));
+        assert_eq!(game.pile_len(), 2);
+    }
+
+    #[test]
+    fn ask_errors() {
+        let mut playing = game(
+            vec![
+                vec![card(0, 0), card(0, 1)],
+                vec![card(0, 2), card(1, 0)],
+                vec![card(1, 1)],
+            ],
+            vec![card(1, 2)],
+            0,
+        );
+        let ask = |asker, target, card| Ask {
+            asker,
+            target,
+            card,
+        };
+
+        assert_eq!(
+            playing.ask(ask(1, 0, card(0, 0))),
+            Err(AskError::NotYourTurn)
+        );
+        assert_eq!(
+            playing.ask(ask(0, 0, card(0, 2))),
+            Err(AskError::InvalidTarget)
+        );
+        assert_eq!(
+            playing.ask(ask(0, 3, card(0, 2))),
+            Err(AskError::InvalidTarget)
+        );
+        assert_eq!(
+            playing.ask(ask(0, 1, card(0, 1))),
+            Err(AskError::AlreadyHeld)
+        );
+        assert_eq!(
+            playing.ask(ask(0, 1, card(1, 0))),
+            Err(AskError::FamilyNotHeld)
+        );
+        // Nothing changed.
+        assert_eq!(playing.current_player(), 0);
+        assert_eq!(playing.hand(1), [card(0, 2), card(1, 0)]);
+
+        let mut over = game(vec![Vec::new(), Vec::new()], Vec::new(), 0);
+        assert_eq!(over.ask(ask(0, 1, card(0, 0))), Err(AskError::GameOver));
+    }
+
+    #[test]
+    fn ask_outcomes() {
+        let hands = || {
+            vec![
+                vec![card(0, 0)],
+                vec![card(0, 1), card(1, 0)],
+                vec![card(1, 1)],
+            ]
+        };
+        let ask = |target, card| Ask {
+            asker: 0,
+            target,
+            card,
+        };
+
+        // The target has the card: the asker plays again.
+        let mut given = game(hands(), vec![card(1, 2)], 0);
+        let result = given.ask(ask(1, card(0, 1))).unwrap();
+        assert_eq!(result.outcome, AskOutcome::Given);
+        assert_eq!(result.completed, None);
+        assert_eq!(given.hand(0), [card(0, 0), card(0, 1)]);
+        assert_eq!(given.hand(1), [card(1, 0)]);
+        assert_eq!(given.current_player(), 0);
+
+        // Drawing the card asked for: the asker plays again.
+        let mut lucky = game(hands(), vec![card(1, 2), card(0, 2)], 0);
+        let result = lucky.ask(ask(2, card(0, 2))).unwrap();
+        assert_eq!(result.outcome, AskOutcome::LuckyDraw);
+        assert_eq!(lucky.hand(0), [card(0, 0), card(0, 2)]);
+        assert_eq!(lucky.pile_len(), 1);
+        assert_eq!(lucky.current_player(), 0);
+
+        // Drawing another card: the turn passes.
+        let mut drew = game(hands(), vec![card(1, 2)], 0);
+        let result = drew.ask(ask(2, card(0, 2))).unwrap();
+        assert_eq!(result.outcome, AskOutcome::Drew(card(1, 2)));
+        assert_eq!(drew.hand(0), [card(0, 0), card(1, 2)]);
+        assert_eq!(drew.current_player(), 1);
+
+        // Nothing to draw: the turn passes.
+        let mut empty = game(hands(), Vec::new(), 0);
+        let result = empty.ask(ask(2, card(0, 2))).unwrap();
+        assert_eq!(result.outcome, AskOutcome::EmptyPile);
+        assert_eq!(empty.hand(0), [card(0, 0)]);
+        assert_eq!(empty.current_player(), 1);
+
+        // The turn passes from the last player to the first.
+        let mut wrap = game(hands(), Vec::new(), 2);
+        let result = wrap.ask(Ask {
+            asker: 2,
+            target: 0,
+            card: card(1, 2),
+        });
+        assert_eq!(result.unwrap().outcome, AskOutcome::EmptyPile);
+        assert_eq!(wrap.current_player(), 0);
+    }
+
+    #[test]
+    fn completed_family_is_put_down() {
+        let mut game = game(
+            vec![
+                (0..5).map(|e| card(0, e)).collect(),
+                vec![card(0, 5), card(1, 0)],
+            ],
+            vec![card(1, 1)],
+            0,
+        );
+
+        let result = game
+            .ask(Ask {
+                asker: 0,
+                target: 1,
+                card: card(0, 5),
+            })
+            .unwrap();
+        assert_eq!(result.outcome, AskOutcome::Given);
+        assert_eq!(result.completed, Some(family(0)));
+        assert_eq!(game.completed(0), [family(0)]);
+        // The hand was emptied by the family put down, a card was drawn to keep playing.
+        assert_eq!(game.hand(0), [card(1, 1)]);
+        assert_eq!(game.pile_len(), 0);
+        assert_eq!(game.current_player(), 0);
+    }
+
+    #[test]
+    fn completed_family_is_put_down_on_deal() {
+        install_deck();
+        let small = families(&[0, 6]);
+
+        // With 9 cards for 2 players, some deals give a whole family of 3 cards.
+        let game = (0..100)
+            .filter_map(|seed| deal(&small, 2, seed).ok())
+            .find(|game| game.scores().iter().any(|&score| score > 0))
+            .expect("a deal with a complete family");
+
+        assert_eq!(game.scores().iter().sum::<usize>(), 1);
+        let complete = game.completed.iter().flatten().copied().next().unwrap();
+        assert_eq!(complete, family(6));
+        assert!(game.hands.iter().flatten().all(|c| c.family() != complete));
+        assert_eq!(card_count(&game), 9);
+    }
+
+    #[test]
+    fn ensure_can_play_with_empty_hand() {
+        // The current player draws to have something to ask for.
+        let mut draws = game(vec![Vec::new(), vec![card(0, 0)]], vec![card(1, 0)], 0);
+        draws.ensure_can_play();
+        assert_eq!(draws.hand(0), [card(1, 0)]);
+        assert_eq!(draws.current_player(), 0);
+
+        // Nothing to draw, the turn passes.
+        let mut passes = game(vec![Vec::new(), vec![card(0, 0)]], Vec::new(), 0);
+        passes.ensure_can_play();
+        assert_eq!(passes.hand(0), []);
+        assert_eq!(passes.current_player(), 1);
+
+        // The turn passes after an empty pile to a player without cards.
+        let mut skips = game(
+            vec![vec![card(0, 0)], Vec::new(), vec![card(0, 1)]],
+            Vec::new(),
+            0,
+        );
+        let result = skips.ask(Ask {
+            asker: 0,
+            target: 2,
+            card: card(0, 2),
+        });
+        assert_eq!(result.unwrap().outcome, AskOutcome::EmptyPile);
+        assert_eq!(skips.current_player(), 2);
+    }
+
+    #[test]
+    fn end_of_game() {
+        let mut game = game(vec![Vec::new(); 3], vec![card(2, 0)], 0);
+        game.completed = vec![vec![family(0)], vec![family(1)], Vec::new()];
+        assert!(!game.is_over());
+
+        game.pile.clear();
+        assert!(game.is_over());
+        assert_eq!(game.scores(), [1, 1, 0]);
+        assert_eq!(game.winners(), [0, 1]);
+
+        game.completed[2] = vec![family(2), family(3)];
+        assert_eq!(game.scores(), [1, 1, 2]);
+        assert_eq!(game.winners(), [2]);
+    }
+}
@@ -0,0 +1,254 @@
+//! Deck used by the game: families, their elements and the files for both.
+//!
+//! The deck is described by the [manifest][crate::manifest] in `assets/deck.toml`,
+//! fetched once on startup (see [`Deck::fetch()`]) so that new vocabulary can be
+//! added without recompiling the game.
+//!
+//! Once [installed][Deck::install()], it is available for the whole lifetime of the page
+//! through [`get()`], which is what [`Family`][crate::family::Family] and
+//! [`Sentence`][crate::sentences::Sentence] use under the hood.
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response;
+
+use crate::manifest::{self, Gender, Manifest};
+
+/// The deck, set once the manifest has been loaded.
+static DECK: OnceLock<Deck> = OnceLock::new();
+
+/// The deck currently in use.
+///
+/// # Panics
+///
+/// If called before [`Deck::install()`].
+pub fn get() -> &'static Deck {
+    DECK.get()
+        .expect("the deck must be loaded before being used")
+}
+
+/// Path of an asset, absolute from the root of the website.
+///
+/// Adapted to github pages.
+pub fn asset_url(path: &str) -> String {
+    if crate::IS_FOR_GH_PAGES {
+        format!("/seven-families-wasm/{path}")
+    } else {
+        format!("/{path}")
+    }
+}
+
+/// Checked version of the [`Manifest`], with all paths resolved.
+#[derive(Debug)]
+pub struct Deck {
+    /// Families, in display order.
+    pub families: Vec<FamilyData>,
+}
+
+/// Everything known about a family.
+#[derive(Debug)]
+pub struct FamilyData {
+    /// Identifier in the manifest, stable across versions of the deck.
+    pub id: String,
+    /// Name shown to players.
+    pub name: String,
+    /// CSS color of the family.
+    pub color: String,
+    /// Path to the logo, absolute from the root of the website.
+    pub logo_file: String,
+    /// Path to the family sound, absolute from the root of the website.
+    pub sound_file: Option<String>,
+    /// Elements of the family, in manifest order.
+    pub elements: Vec<ElementData>,
+}
+
+/// Everything known about an element.
+#[derive(Debug)]
+pub struct ElementData {
+    /// Identifier in the manifest, unique inside its family.
+    pub id: String,
+    /// Name shown to players.
+    pub label: String,
+    /// Path to the element sound, absolute from the root of the website.
+    pub sound_file: Option<String>,
+    /// Path to the element illustration, absolute from the root of the website.
+    pub image_file: Option<String>,
+    /// Grammatical gender, for nouns.
+    pub gender: Option<Gender>,
+    /// Definite article, for nouns.
+    pub article: Option<String>,
+    /// Short definition.
+    pub definition: Option<String>,
+    /// Example of the element used in a sentence.
+    pub example: Option<String>,
+    /// Pronunciation in the International Phonetic Alphabet, without slashes.
+    pub ipa: Option<String>,
+    /// Label split in syllables.
+    pub syllables: Option<String>,
+}
+
+impl Deck {
+    /// Fetch the manifest from the website and check it.
+    pub async fn fetch() -> Result<Self, DeckError> {
+        let window = web_sys::window().ok_or(DeckError::Fetch(None))?;
+        let response: Response =
+            JsFuture::from(window.fetch_with_str(&asset_url(manifest::MANIFEST_PATH)))
+                .await
+                .map_err(DeckError::from_js)?
+                .unchecked_into();
+        if !response.ok() {
+            return Err(DeckError::Fetch(Some(format!(
+                "HTTP {}",
+                response.status()
+            ))));
+        }
+
+        let text = JsFuture::from(response.text().map_err(DeckError::from_js)?)
+            .await
+            .map_err(DeckError::from_js)?;
+        Self::parse(&text.as_string().unwrap_or_default())
+    }
+
+    /// Parse and check the content of a manifest.
+    pub fn parse(text: &str) -> Result<Self, DeckError> {
+        let manifest: Manifest = toml::from_str(text).map_err(DeckError::Parse)?;
+        Self::from_manifest(manifest)
+    }
+
+    /// Check a manifest and resolve its paths.
+    pub fn from_manifest(manifest: Manifest) -> Result<Self, DeckError> {
+        if manifest.families.is_empty() {
+            return Err(DeckError::Invalid("aucune famille".into()));
+        }
+
+        let mut family_ids = HashSet::new();
+        let mut families = Vec::with_capacity(manifest.families.len());
+        for family in manifest.families {
+            if !family_ids.insert(family.id.clone()) {
+                return Err(DeckError::Invalid(format!(
+                    "famille '{}' déclarée deux fois",
+                    family.id
+                )));
+            }
+            if !(manifest::MIN_ELEMENTS..=manifest::MAX_ELEMENTS).contains(&family.elements.len()) {
+                return Err(DeckError::Invalid(format!(
+                    "famille '{}' avec {} éléments, il en faut entre {} et {}",
+                    family.id,
+                    family.elements.len(),
+                    manifest::MIN_ELEMENTS,
+                    manifest::MAX_ELEMENTS,
+                )));
+            }
+
+            let file = |name: &str| asset_url(&format!("assets/{}/{name}", family.id));
+
+            let mut element_ids = HashSet::new();
+            let mut elements = Vec::with_capacity(family.elements.len());
+            for element in &family.elements {
+                if !element_ids.insert(element.id.as_str()) {
+                    return Err(DeckError::Invalid(format!(
+                        "élément '{}' déclaré deux fois dans la famille '{}'",
+                        element.id, family.id
+                    )));
+                }
+                elements.push(ElementData {
+                    id: element.id.clone(),
+                    label: element.label.clone(),
+                    sound_file: element.sound.as_deref().map(file),
+                    image_file: element.image.as_deref().map(file),
+                    gender: element.gender,
+                    article: element.article.clone(),
+                    definition: element.definition.clone(),
+                    example: element.example.clone(),
+                    ipa: element.ipa.clone(),
+                    syllables: element.syllables.clone(),
+                });
+            }
+
+            families.push(FamilyData {
+                logo_file: file(&family.logo),
+                sound_file: family.sound.as_deref().map(file),
+                name: family.name,
+                color: family.color,
+                elements,
+                id: family.id,
+            });
+        }
+
+        Ok(Self { families })
+    }
+
+    /// Make the deck available through [`get()`].
+    ///
+    /// The deck is only loaded once per page, later calls are ignored.
+    pub fn install(self) {
+        DECK.set(self).ok();
+    }
+}
+
+/// Reasons the deck could not be loaded.
+#[derive(Debug)]
+pub enum DeckError {
+    /// The manifest could not be downloaded.
+    Fetch(Option<String>),
+    /// The manifest is not valid TOML or misses some fields.
+    Parse(toml::de::Error),
+    /// The manifest is valid TOML but its content is not usable.
+    Invalid(String),
+}
+
+impl DeckError {
+    fn from_js(value: wasm_bindgen::JsValue) -> Self {
+        Self::Fetch(value.as_string())
+    }
+}
+
+impl std::fmt::Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetch(None) => write!(f, "{} inaccessible", manifest::MANIFEST_PATH),
+            Self::Fetch(Some(reason)) => {
+                write!(f, "{} inaccessible ({reason})", manifest::MANIFEST_PATH)
+            }
+            Self::Parse(err) => write!(f, "{} illisible: {err}", manifest::MANIFEST_PATH),
+            Self::Invalid(reason) => write!(f, "{} invalide: {reason}", manifest::MANIFEST_PATH),
+        }
+    }
+}
+
+/// Deck shared by the tests of every module.
+#[cfg(test)]
+pub mod test_deck {
+    use super::Deck;
+    use crate::family::Family;
+    use crate::sentences::Sentence;
+
+    /// Install a deck of 6 families of 6 elements and a last one of 3.
+    ///
+    /// The deck is global, every test installs the same one.
+    pub fn install_deck() {
+        let mut manifest = String::new();
+        for f in 0..7 {
+            manifest += &format!(
+                "[[family]]\nid = \"f{f}\"\nname = \"F{f}\"\ncolor = \"red\"\nlogo = \"logo.png\"\n\n"
+            );
+            for e in 0..if f < 6 { 6 } else { 3 } {
+                manifest += &format!("[[family.element]]\nid = \"e{e}\"\nlabel = \"E{e}\"\n\n");
+            }
+        }
+        Deck::parse(&manifest).unwrap().install();
+    }
+
+    /// Family number `f` of the deck, installing it first.
+    pub fn family(f: usize) -> Family {
+        install_deck();
+        Family::all().nth(f).unwrap()
+    }
+
+    /// Element number `e` of family number `f`.
+    pub fn card(f: usize, e: usize) -> Sentence {
+        Sentence::all_in(family(f)).nth(e).unwrap()
+    }
+}
@@ -0,0 +1,210 @@
+//! Dictation of the elements.
+//!
+//! See [`Dictation`].
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::audio::Audio;
+use crate::score::{Mistake, Score};
+use crate::sentences::{shuffled, Sentence};
+use crate::spelling::{self, Strictness};
+use crate::style;
+
+/// Dictation component: an element is said, the student types it and sees their
+/// mistakes letter by letter.
+pub struct Dictation {
+    /// Says the elements.
+    audio: Audio,
+    /// How close the answers must be to the labels.
+    strictness: Strictness,
+    /// Elements left to dictate, the next one is last.
+    remaining: Vec<Sentence>,
+    /// What the student typed so far.
+    input: String,
+    /// Answers so far.
+    score: Score,
+    /// What is shown to the student.
+    stage: Stage,
+}
+
+/// What is shown to the student.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    /// Choosing the strictness, browsers only play sounds after a click anyway.
+    Start,
+    /// The element was said, waiting for the student to type it.
+    Question(Sentence),
+    /// The student answered, showing their mistakes.
+    Answered { sentence: Sentence, right: bool },
+    /// All the elements were dictated.
+    Over,
+}
+
+#[derive(Debug)]
+pub enum DictationMsg {
+    /// Change how close the answers must be to the labels.
+    SetStrictness(Strictness),
+    /// Go to the next element, or start the dictation.
+    Next,
+    /// Say the element again.
+    Replay,
+    /// The typed text changed.
+    Input(String),
+    /// Check the typed text.
+    Submit,
+    /// Dictate all the elements again, in another order.
+    Restart,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct DictationProps {
+    /// Elements to dictate.
+    pub selection: Vec<Sentence>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for Dictation {
+    type Message = DictationMsg;
+    type Properties = DictationProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            audio: Audio::new(|_| ()),
+            strictness: Strictness::IgnoreAccentsAndCase,
+            remaining: shuffled(&ctx.props().selection),
+            input: String::new(),
+            score: Score::default(),
+            stage: Stage::Start,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match (self.stage, msg) {
+            (Stage::Start, DictationMsg::SetStrictness(strictness)) => {
+                self.strictness = strictness;
+            }
+            (Stage::Start | Stage::Answered { .. }, DictationMsg::Next) => {
+                self.input.clear();
+                self.stage = match self.remaining.pop() {
+                    Some(sentence) => {
+                        self.audio.play(&sentence.element_sound());
+                        Stage::Question(sentence)
+                    }
+                    None => Stage::Over,
+                };
+            }
+            (Stage::Question(sentence), DictationMsg::Replay) => {
+                self.audio.play(&sentence.element_sound());
+                return false;
+            }
+            (Stage::Question(_), DictationMsg::Input(input)) => {
+                self.input = input;
+                return false;
+            }
+            (Stage::Question(sentence), DictationMsg::Submit) => {
+                self.audio.pause();
+                let right = spelling::is_right(sentence.label(), &self.input, self.strictness);
+                if right {
+                    self.score.right(sentence);
+                } else {
+                    self.score.wrong(Mistake {
+                        sentence,
+                        expected: sentence.label().to_owned(),
+                        given: self.input.trim().to_owned(),
+                    });
+                }
+                self.stage = Stage::Answered { sentence, right };
+            }
+            (Stage::Over, DictationMsg::Restart) => {
+                self.remaining = shuffled(&ctx.props().selection);
+                self.score = Score::default();
+                self.stage = Stage::Start;
+            }
+            (_, DictationMsg::Exit) => {
+                self.audio.pause();
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let content = match self.stage {
+            Stage::Start => html! {
+                <>
+                    <p> { "Écoutez chaque mot et écrivez-le." } </p>
+                    { for Strictness::ALL.into_iter().map(|strictness| html! {
+                        <button
+                            onclick={ link.callback(move |_| DictationMsg::SetStrictness(strictness)) }
+                            class={ style::button_select_family(strictness == self.strictness) }
+                        >
+                            { strictness.label() }
+                        </button>
+                    }) }
+                    <hr />
+                    <button onclick={ link.callback(|_| DictationMsg::Next) }> { "Commencer" } </button>
+                </>
+            },
+            Stage::Question(_) => html! {
+                <>
+                    <button onclick={ link.callback(|_| DictationMsg::Replay) }> { "Réécouter" } </button>
+                    <form onsubmit={ link.callback(|e: FocusEvent| {
+                        e.prevent_default();
+                        DictationMsg::Submit
+                    }) }>
+                        <input
+                            type="text"
+                            autocomplete="off"
+                            autocapitalize="off"
+                            spellcheck="false"
+                            oninput={ link.callback(|e: InputEvent| {
+                                // Unchecked: we define the callback inside the element it concerns, we cannot
+                                // be referencing the wrong one.
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                DictationMsg::Input(input.value())
+                            }) }
+                        />
+                        <button type="submit"> { "Valider" } </button>
+                    </form>
+                    <hr />
+                    { self.score.render() }
+                </>
+            },
+            Stage::Answered { sentence, right } => html! {
+                <>
+                    if right {
+                        <p> { format!("Bonne réponse : {}", sentence.label()) } </p>
+                    } else {
+                        <p> { format!("La bonne orthographe est : {}", sentence.label()) } </p>
+                        <p> { "Votre réponse : " } { spelling::render_diff(&spelling::diff(sentence.label(), &self.input, self.strictness)) } </p>
+                    }
+                    <hr />
+                    { self.score.render() }
+                    <button onclick={ link.callback(|_| DictationMsg::Next) }> { "Mot suivant" } </button>
+                </>
+            },
+            Stage::Over => html! {
+                <>
+                    { self.score.render_recap() }
+                    <button onclick={ link.callback(|_| DictationMsg::Restart) }> { "Recommencer" } </button>
+                </>
+            },
+        };
+
+        html! {
+            <>
+                { content }
+                <hr />
+                <button onclick={ link.callback(|_| DictationMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+            </>
+        }
+    }
+}
@@ -1,38 +1,39 @@
 use std::borrow::Cow;
 
-use enum_iterator::IntoEnumIterator;
 use yew::html::Scope;
 use yew::prelude::*;
 
+use crate::deck::{self, FamilyData};
 use crate::game::{BeforeGameMsg, Game};
 use crate::style;
 
 /// Families without the sentences in them.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IntoEnumIterator)]
-pub enum Family {
-    /// Ustensils used by a chef when cooking.
-    ChiefKit,
-    /// Other fruits, like oranges.
-    Fruits,
-    /// Hygiene is important for a cook.
-    Hygiene,
-    /// Flipping pancakes is a professional gesture.
-    ProfessionalGestures,
-    /// Red fruits, like strawberries.
-    RedFruits,
-    /// Small tools used in cooking that are not part
-    /// of a chief's kit.
-    SmallUstensils,
-    /// Trimmings, like cutting cucumbers.
-    Trimmings,
-}
+///
+/// A family is an index in the [deck][crate::deck], only valid once the deck
+/// has been loaded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Family(usize);
 
 impl Family {
+    /// All the families of the deck, in display order.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..deck::get().families.len()).map(Self)
+    }
+
+    /// Data for the family in the deck.
+    pub(crate) fn data(&self) -> &'static FamilyData {
+        &deck::get().families[self.0]
+    }
+
     /// Render the family's button, adapting to whether it is selected or not.
     pub fn render(&self, link: &Scope<Game>, selected: bool) -> Html {
         let f = *self;
-        let onclick = link.callback(move |_| BeforeGameMsg::Toggle(f));
+        self.render_button(link.callback(move |_| BeforeGameMsg::Toggle(f)), selected)
+    }
 
+    /// Render the family's button with any `onclick`, adapting to whether it is
+    /// selected or not.
+    pub fn render_button(&self, onclick: Callback<MouseEvent>, selected: bool) -> Html {
         html! {
             <button {onclick} class={style::button_select_family(selected)} style={self.button_style(selected)}>
                 <img
@@ -70,55 +71,17 @@ impl Family {
 
     /// Color associated with the family.
     pub fn color(&self) -> &'static str {
-        match self {
-            Self::ChiefKit => "purple",
-            Self::Fruits => "orange",
-            Self::Hygiene => "blue",
-            Self::ProfessionalGestures => "black",
-            Self::RedFruits => "red",
-            Self::SmallUstensils => "gray",
-            Self::Trimmings => "darkgreen",
-        }
+        &self.data().color
     }
 
     /// Path to logo file.
     pub fn logo_file(&self) -> &'static str {
-        macro_rules! logo_image_file {
-            ($folder:literal) => {{
-                // Check for file existence at compile-time
-                const _: &[u8] =
-                    include_bytes!(concat!("../assets/", $folder, "/0-logo.png")).as_slice();
-                // Adapt file path after checking if we're running on github pages or no
-                if crate::IS_FOR_GH_PAGES {
-                    concat!("/seven-families-wasm/assets/", $folder, "/0-logo.png")
-                } else {
-                    concat!("/assets/", $folder, "/0-logo.png")
-                }
-            }};
-        }
-
-        match self {
-            Self::ChiefKit => logo_image_file!("mallette"),
-            Self::Fruits => logo_image_file!("fruits"),
-            Self::Hygiene => logo_image_file!("hygiene"),
-            Self::ProfessionalGestures => logo_image_file!("gestes-professionnels"),
-            Self::RedFruits => logo_image_file!("fruits-rouges"),
-            Self::SmallUstensils => logo_image_file!("petit-materiel"),
-            Self::Trimmings => logo_image_file!("taillages"),
-        }
+        &self.data().logo_file
     }
 }
 
 impl std::fmt::Display for Family {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::ChiefKit => f.write_str("Mallette"),
-            Self::Fruits => f.write_str("Fruits"),
-            Self::Hygiene => f.write_str("Hygiène"),
-            Self::ProfessionalGestures => f.write_str("Gestes Professionnels"),
-            Self::RedFruits => f.write_str("Fruits Rouges"),
-            Self::SmallUstensils => f.write_str("Petit Matériel"),
-            Self::Trimmings => f.write_str("Taillages"),
-        }
+        f.write_str(&self.data().name)
     }
 }
@@ -4,17 +4,30 @@
 use std::collections::HashSet;
 use std::time::Duration;
 
-use enum_iterator::IntoEnumIterator;
 use gloo_timers::callback::Interval;
-use web_sys::HtmlInputElement;
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::html::Scope;
 use yew::prelude::*;
 
 use crate::audio::Audio;
+use crate::bingo::{self, Grid};
+use crate::bingo_cards::{BingoCards, BingoCardsProps};
+use crate::deck::{Deck, DeckError};
+use crate::dictation::{Dictation, DictationProps};
 use crate::family::Family;
-use crate::sentences::{Sentence, Sentences};
+use crate::glossary::{Glossary, GlossaryProps};
+use crate::history::History;
+use crate::hot_seat::{HotSeat, HotSeatProps};
+use crate::odd_one_out::{OddOneOut, OddOneOutProps};
+use crate::picture_match::{PictureMatch, PictureMatchProps};
+use crate::revision::{Revision, RevisionProps};
+use crate::saved_game::{SavedGame, SavedState};
+use crate::sentences::{DrawStrategy, Sentence, Sentences};
+use crate::solo::{Solo, SoloProps};
 use crate::style;
 use crate::timer::Timer;
+use crate::which_family::{WhichFamily, WhichFamilyProps};
 
 /// Minimum time between two sentences.
 const MIN_TIMER_DURATION: Duration = Duration::from_secs(3);
@@ -24,6 +37,8 @@ const MAX_TIMER_DURATION: Duration = Duration::from_secs(60);
 const MIN_TIMER_DURATION_STR: &str = "3";
 /// String representation for javascript.
 const MAX_TIMER_DURATION_STR: &str = "60";
+/// Maximum number of draws for [`DrawStrategy::WithReplacement`].
+const MAX_DRAW_ROUNDS: usize = 999;
 
 /// Game component.
 ///
@@ -43,15 +58,27 @@ pub struct Game {
     /// element has been created after, it is not taken into account.
     ///
     /// This is fixed by creating the audio element once on the first load of the page and
-    /// simply upadting its [`src`][Audio::set_src()] element each time we switch to the next
+    /// simply [playing][Audio::play()] a new sound from it each time we switch to the next
     /// element or sentence.
     audio: Audio,
     /// Time interval between each sentence.
     ///
     /// See [`MIN_TIMER_DURATION`], [`MAX_TIMER_DURATION`] and [`State::Waiting`].
     duration: Duration,
+    /// How the sentences are drawn, kept from one game to the next.
+    strategy: DrawStrategy,
+    /// Show the syllables and IPA of the elements under the transcript, kept
+    /// from one game to the next.
+    phonetics: bool,
     /// The sentences selected to play the game.
     sentences: Sentences,
+    /// The sentences already drawn in the current game.
+    history: History,
+    /// Grids of the bingo the current game is the draw of, empty if it is not one.
+    bingo: Vec<Grid>,
+    /// Game saved before the page was reloaded, offered on the family selection
+    /// until another game is started.
+    saved: Option<SavedGame>,
     /// State of the game.
     state: State,
 }
@@ -60,7 +87,12 @@ impl std::fmt::Debug for Game {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Game")
             .field("duration", &self.duration)
+            .field("strategy", &self.strategy)
+            .field("phonetics", &self.phonetics)
             .field("sentences", &self.sentences)
+            .field("history", &self.history)
+            .field("bingo", &self.bingo)
+            .field("saved", &self.saved)
             .field("state", &self.state)
             .finish()
     }
@@ -71,8 +103,16 @@ impl std::fmt::Debug for Game {
 pub enum State {
     // Don't sort alphabetically here, we want to follow the flow of the game.
     // ----
+    /// Waiting for the [deck][crate::deck] to be fetched.
+    LoadingDeck,
+    /// The deck could not be loaded, nothing can be played.
+    DeckError(DeckError),
+    /// Choosing the families, or single elements, to play with.
     SelectingFamilies {
-        families: HashSet<Family>,
+        /// Elements selected to play.
+        selection: HashSet<Sentence>,
+        /// Families whose elements are shown for individual selection.
+        expanded: HashSet<Family>,
     },
     /// Waiting for permission to play sound.
     ///
@@ -87,18 +127,25 @@ pub enum State {
     Playing {
         /// The sentence and at which point of if the game is.
         current: (Sentence, SentenceState),
+        /// When [replaying][InGameMsg::Replay], what was left of the countdown
+        /// to the next sentence, restored once the sentence has been said again.
+        countdown: Option<Duration>,
     },
     /// Playing is paused.
     PlayingPaused {
         /// The sentence to resume and which part of it.
         current: (Sentence, SentenceState),
+        /// See [`State::Playing`].
+        countdown: Option<Duration>,
     },
     /// Waiting for the next sentence.
     Waiting {
+        /// The sentence that was just said.
+        last: Sentence,
         // Both `Interval` and `Timer` are cancelled on drop.
         /// Sends a message each second to update the countdown
         /// to the [next sentence][InGameMsg::NextSentence].
-        seconds: Interval,
+        _seconds: Interval,
         /// Countdown display to the next sentence.
         time_left: Duration,
         /// Coutdown to the next sentence, will send a message
@@ -107,15 +154,87 @@ pub enum State {
     },
     /// Waiting for the next sentence is paused.
     WaitingPaused {
+        /// The sentence that was just said.
+        last: Sentence,
         /// What's left of the countdown to the next sentence.
         time_left: Duration,
     },
     /// Game is finished.
     Finished,
+    /// Playing another [mode][Mode] with the selection, the mode handles everything
+    /// until it sends [`InGameMsg::GoHome`].
+    Mode {
+        mode: Mode,
+        /// Elements selected to play, restored when going home.
+        selection: HashSet<Sentence>,
+    },
+}
+
+/// Other ways to play with the selected elements, besides the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Seven families card game, several players on the same device.
+    HotSeat,
+    /// Seven families card game, alone against computer opponents.
+    Solo,
+    /// Listening quiz, finding the family of each element.
+    WhichFamily,
+    /// Typing each element after hearing it.
+    Dictation,
+    /// Finding the word matching the illustration of each element.
+    PictureMatch,
+    /// Finding the element that is not from the same family as the others.
+    OddOneOut,
+    /// Printing bingo grids, then using the caller as the bingo draw.
+    Bingo,
+    /// Spaced repetition of the elements due today, whatever the selection.
+    Revision,
+    /// Browsing every family and element of the deck.
+    Glossary,
+}
+
+impl Mode {
+    /// All the modes played with the selection, in display order.
+    ///
+    /// [`Mode::Revision`] and [`Mode::Glossary`] are launched from their own buttons,
+    /// they need no selection.
+    const ALL: [Self; 7] = [
+        Self::HotSeat,
+        Self::Solo,
+        Self::WhichFamily,
+        Self::Dictation,
+        Self::PictureMatch,
+        Self::OddOneOut,
+        Self::Bingo,
+    ];
+
+    /// `false` if the mode has nothing to play with `selection`, its button is
+    /// then hidden: [`Mode::PictureMatch`] needs elements with an illustration.
+    fn is_playable(&self, selection: &HashSet<Sentence>) -> bool {
+        match self {
+            Self::PictureMatch => selection.iter().any(|st| st.image_file().is_some()),
+            _ => true,
+        }
+    }
+
+    /// Name of the mode, as shown to players.
+    fn label(&self) -> &'static str {
+        match self {
+            Self::HotSeat => "Jeu de cartes à plusieurs",
+            Self::Solo => "Jeu de cartes contre des robots",
+            Self::WhichFamily => "Quiz : quelle famille ?",
+            Self::Dictation => "Dictée",
+            Self::PictureMatch => "Associer l'image et le mot",
+            Self::OddOneOut => "L'intrus",
+            Self::Bingo => "Bingo",
+            Self::Revision => "Réviser",
+            Self::Glossary => "Glossaire",
+        }
+    }
 }
 
 /// A sentence is composed of two parts (with regard to the sound files).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum SentenceState {
     /// Second part of the sentence, unique for each element.
     Element,
@@ -125,6 +244,8 @@ pub enum SentenceState {
 
 #[derive(Debug)]
 pub enum Msg {
+    /// The [deck][crate::deck] was fetched, or failed to.
+    DeckLoaded(Result<Deck, DeckError>),
     Before(BeforeGameMsg),
     InGame(InGameMsg),
 }
@@ -133,14 +254,24 @@ pub enum Msg {
 /// game itself.
 #[derive(Debug)]
 pub enum BeforeGameMsg {
-    /// Toggle selection for a family.
+    /// Toggle selection for a whole family.
+    ///
+    /// If only some of its elements were selected, the whole family is.
     Toggle(Family),
+    /// Toggle selection for a single element.
+    ToggleElement(Sentence),
+    /// Show or hide the elements of a family.
+    ToggleExpanded(Family),
     /// Select all families.
     SelectAllFamilies,
     /// Deselect all families.
     ClearAllFamilies,
-    /// Launch the game with the selected families.
+    /// Launch the game with the selected elements.
     LaunchGame,
+    /// Launch another mode with the selected elements.
+    LaunchMode(Mode),
+    /// Resume the game saved before the page was reloaded, paused.
+    ResumeGame,
 }
 
 /// Messages sent during the lifetime of a [`Game`].
@@ -149,14 +280,30 @@ pub enum InGameMsg {
     /// Update the start duration of the countdown
     /// to the next sentence.
     ChangeTimer(u64),
+    /// Shuffle the sentences again with the given seed, before the game
+    /// has started.
+    ChangeSeed(u64),
+    /// Order the sentences again with the given strategy, before the game
+    /// has started.
+    ChangeStrategy(DrawStrategy),
     /// Go back to the starting state, selecting families.
     GoHome,
+    /// Show or hide the pronunciation of the elements under the transcript.
+    TogglePhonetics,
+    /// Launch the game as the draw of a bingo played with the given grids.
+    LaunchBingo(Vec<Grid>),
     /// Launch next sentence sound.
     NextSentence,
     /// Pause the game.
     Pause,
+    /// Say the last sentence again, then go back to the countdown
+    /// to the next sentence where it was.
+    Replay,
     /// Resume playing.
     Resume,
+    /// Put the last sentence back and go back to the countdown before it
+    /// was drawn.
+    Undo,
     /// Either the first or second sound of a whole
     /// sentence just completed.
     SentenceState,
@@ -174,6 +321,7 @@ impl Component for Game {
 
     fn create(ctx: &Context<Self>) -> Self {
         let link = ctx.link();
+        link.send_future(async { Msg::DeckLoaded(Deck::fetch().await) });
 
         let link = link.clone();
         let audio = Audio::new(move |_| link.send_message(InGameMsg::SentenceState));
@@ -181,17 +329,21 @@ impl Component for Game {
         Self {
             audio,
             duration: Duration::from_secs(20),
-            /// Sentences are empty at first
-            sentences: Sentences::new(&Default::default()),
-            state: State::SelectingFamilies {
-                families: Default::default(),
-            },
+            strategy: DrawStrategy::Shuffle,
+            phonetics: false,
+            // Sentences are empty at first
+            sentences: Sentences::new(&Default::default(), 0, DrawStrategy::Shuffle),
+            history: Default::default(),
+            bingo: Vec::new(),
+            saved: None,
+            state: State::LoadingDeck,
         }
     }
 
     fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
         if let State::Playing {
             current: (st, state),
+            ..
         } = &self.state
         {
             // The source is reset on each render of the `Playing` state.
@@ -200,18 +352,31 @@ impl Component for Game {
             // in the sound. This is voluntary: the sound are very short and makes little
             // to no sense if taken mid-step, a bad combination for a game intended for
             // people learning Frennch.
-            self.audio.set_src(match state {
-                SentenceState::Element => st.element_sound_file(),
-                SentenceState::Family => st.family_sound_file(),
+            self.audio.play(&match state {
+                SentenceState::Element => st.element_sound(),
+                SentenceState::Family => st.family_sound(),
             });
-            self.audio.play();
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::Before(msg) => self.update_before_game(msg),
-            Msg::InGame(msg) => self.update_in_game(ctx, msg),
+            Msg::DeckLoaded(deck) => self.update_deck_loaded(deck),
+            Msg::Before(msg) => {
+                let render = self.update_before_game(msg);
+                self.save();
+                render
+            }
+            Msg::InGame(msg) => {
+                // The countdown is not a transition, saving it each second would be
+                // wasteful: the game is resumed paused anyway.
+                let transition = !matches!(msg, InGameMsg::UpdateTime);
+                let render = self.update_in_game(ctx, msg);
+                if transition {
+                    self.save();
+                }
+                render
+            }
         }
     }
 
@@ -219,20 +384,42 @@ impl Component for Game {
         let link = ctx.link();
 
         match self.state {
-            State::SelectingFamilies { ref families } => {
+            State::LoadingDeck => html! { <p> { "Chargement des familles ..." } </p> },
+            State::DeckError(ref err) => html! {
+                <p> { format!("Impossible de charger les familles: {err}") } </p>
+            },
+            State::SelectingFamilies {
+                ref selection,
+                ref expanded,
+            } => {
                 let link = ctx.link();
 
                 html! {
                     <div>
+                        if let Some(saved) = &self.saved {
+                            <button onclick={ link.callback(|_| BeforeGameMsg::ResumeGame) }>
+                                { format!(
+                                    "Reprendre la partie en cours ({} phrases sur {})",
+                                    saved.sentences.drawn(),
+                                    saved.sentences.total()
+                                ) }
+                            </button>
+                            <hr />
+                        }
                         <button onclick={ link.callback(|_| BeforeGameMsg::SelectAllFamilies) } class={style::button_select_all("#4CAF50")}>
                             { "Sélectionner toutes les familles" }
                         </button>
                         <button onclick={ link.callback(|_| BeforeGameMsg::ClearAllFamilies) } class={style::button_select_all("#F44336")}>
                             { "Tout déselectionner" }
                         </button>
+                        { for [Mode::Revision, Mode::Glossary].into_iter().map(|mode| html! {
+                            <button onclick={ link.callback(move |_| BeforeGameMsg::LaunchMode(mode)) }>
+                                { mode.label() }
+                            </button>
+                        }) }
                         <hr />
-                        { family_view(link, families) }
-                        { start_button(link, families) }
+                        { family_view(link, selection, expanded) }
+                        { start_button(link, selection) }
                     </div>
                 }
             }
@@ -244,26 +431,68 @@ impl Component for Game {
                     </button>
                     <hr />
                     { timer_slider(link, self.duration) }
+                    { strategy_select(link, self.strategy, self.sentences.selected()) }
+                    { phonetics_checkbox(link, self.phonetics) }
+                    <hr />
+                    { seed_input(link, self.sentences.seed()) }
                 </>
             },
             // State: sound is currently playing.
-            State::Playing { .. } => html! { pause_button(link) },
+            State::Playing {
+                current: (st, state),
+                ..
+            } => html! {
+                <>
+                    { pause_button(link) }
+                    { transcript(st, state, self.phonetics) }
+                    { element_image(st, state) }
+                    { self.history.render_list() }
+                </>
+            },
             // State: sound was paused.
-            State::PlayingPaused { .. } => html! { resume_view(link, self.duration) },
+            State::PlayingPaused {
+                current: (st, state),
+                ..
+            } => html! {
+                <>
+                    { resume_view(link, self.duration) }
+                    { replay_button(link) }
+                    { transcript(st, state, self.phonetics) }
+                    { element_image(st, state) }
+                    { bingo::render_check(&self.bingo, &self.history) }
+                    { self.history.render_list() }
+                </>
+            },
             // State: waiting for the coutdown to the next sentence to end.
-            State::Waiting { time_left, .. } => html! {
+            State::Waiting {
+                last, time_left, ..
+            } => html! {
                 <>
                     { pause_button(link) }
+                    { transcript(last, SentenceState::Element, self.phonetics) }
+                    { element_image(last, SentenceState::Element) }
+                    { replay_button(link) }
+                    { undo_button(link) }
                     { next_sentence_button(link) }
                     <p> { format!("Phrase suivante dans ... {}s", time_left.as_secs()) } </p>
+                    { progress(&self.sentences, last) }
+                    { bingo::render_check(&self.bingo, &self.history) }
+                    { self.history.render_list() }
                 </>
             },
             // State: countdown to next sentence was paused.
-            State::WaitingPaused { time_left, .. } => html! {
+            State::WaitingPaused { last, time_left } => html! {
                 <>
                     { resume_view(link, self.duration) }
+                    { transcript(last, SentenceState::Element, self.phonetics) }
+                    { element_image(last, SentenceState::Element) }
+                    { replay_button(link) }
+                    { undo_button(link) }
                     { next_sentence_button(link) }
                     <p> { format!("Phrase suivante dans ... {}s (Pause)", time_left.as_secs()) } </p>
+                    { progress(&self.sentences, last) }
+                    { bingo::render_check(&self.bingo, &self.history) }
+                    { self.history.render_list() }
                 </>
             },
             // State: game is finished, nothing more to do.
@@ -271,13 +500,36 @@ impl Component for Game {
                 <>
                     { go_home_button(link) }
                     <p> { "Jeu terminé !" } </p>
+                    { bingo::render_check(&self.bingo, &self.history) }
+                    { self.history.render_recap() }
                 </>
             },
+            // State: another mode is played, it handles its own state.
+            State::Mode {
+                mode,
+                ref selection,
+            } => mode_view(link, mode, selection),
         }
     }
 }
 
 impl Game {
+    fn update_deck_loaded(&mut self, deck: Result<Deck, DeckError>) -> bool {
+        self.state = match deck {
+            Ok(deck) => {
+                deck.install();
+                self.saved = SavedGame::load();
+                State::SelectingFamilies {
+                    selection: Default::default(),
+                    expanded: Default::default(),
+                }
+            }
+            Err(err) => State::DeckError(err),
+        };
+
+        true
+    }
+
     fn update_in_game(&mut self, ctx: &Context<Self>, msg: InGameMsg) -> bool {
         match (&mut self.state, msg) {
             // State of game: timer duration was changed before game started or during a pause.
@@ -287,6 +539,19 @@ impl Game {
                     MAX_TIMER_DURATION,
                 );
             },
+            // State of game: seed was changed before the game started, no sentences were drawn yet.
+            (State::GettingSoundPermission, InGameMsg::ChangeSeed(seed)) => {
+                self.sentences.reseed(seed);
+            },
+            // State of game: draw strategy was changed before the game started, no sentences were drawn yet.
+            (State::GettingSoundPermission, InGameMsg::ChangeStrategy(strategy)) => {
+                self.strategy = strategy;
+                self.sentences.set_strategy(strategy);
+            },
+            // State of game: pronunciation was shown or hidden before the game started.
+            (State::GettingSoundPermission, InGameMsg::TogglePhonetics) => {
+                self.phonetics = !self.phonetics;
+            },
             // State: was waiting for permission to play sound, just got it.
             (State::GettingSoundPermission, InGameMsg::SoundPermission)
             // State: launch next sentence (either because the timer just ended or because a "Phrase suivante" button was clicked).
@@ -294,36 +559,71 @@ impl Game {
             | (State::WaitingPaused { .. }, InGameMsg::NextSentence)
             // State: the last sentence was drawn, end the game immediately.
             | (State::Playing { .. }, InGameMsg::NextSentence) => {
+                // Kept in the history to be restored on undo.
+                let countdown = match &mut self.state {
+                    State::GettingSoundPermission => None,
+                    State::Waiting { timer, .. } => Some(timer.stop()),
+                    State::WaitingPaused { time_left, .. } => Some(*time_left),
+                    _ => Some(Duration::ZERO),
+                };
+
                 match self.sentences.draw_one() {
                     None => self.state = State::Finished,
                     Some(st) => {
+                        self.history.push(st, countdown);
                         self.state = State::Playing {
                             current: (st, SentenceState::Family),
+                            countdown: None,
                         }
                     }
                 }
             }
             // State of the game: a sound just finished playing.
-            (State::Playing { current }, InGameMsg::SentenceState) => {
+            (State::Playing { current, countdown }, InGameMsg::SentenceState) => {
                 match current {
                     (st, SentenceState::Family) => *current = (*st, SentenceState::Element),
-                    (_, SentenceState::Element) => {
-                        if self.sentences.is_empty() {
+                    (st, SentenceState::Element) => {
+                        if let Some(time_left) = countdown {
+                            // The sentence was replayed, go back to where the countdown was.
+                            self.state = waiting_state(ctx.link(), *st, *time_left);
+                        } else if self.sentences.is_empty() {
                             self.state = State::Finished;
                         } else {
-                            self.state = waiting_state(ctx.link(), self.duration);
+                            self.state = waiting_state(ctx.link(), *st, self.duration);
                         }
                     }
                 }
             },
             // State of game: a sound is playing
-            (State::Playing { current }, InGameMsg::Pause) => {
+            (State::Playing { current, countdown }, InGameMsg::Pause) => {
                 self.audio.pause();
 
                 self.state = State::PlayingPaused {
                     current: *current,
+                    countdown: *countdown,
                 };
             },
+            // State of game: say the last sentence again, from its start.
+            //
+            // For `Waiting`, this will drop the timer and the interval, cancelling them.
+            (State::Waiting { last, timer, .. }, InGameMsg::Replay) => {
+                self.state = State::Playing {
+                    current: (*last, SentenceState::Family),
+                    countdown: Some(timer.stop()),
+                };
+            }
+            (State::WaitingPaused { last, time_left }, InGameMsg::Replay) => {
+                self.state = State::Playing {
+                    current: (*last, SentenceState::Family),
+                    countdown: Some(*time_left),
+                };
+            }
+            (State::PlayingPaused { current: (st, _), countdown }, InGameMsg::Replay) => {
+                self.state = State::Playing {
+                    current: (*st, SentenceState::Family),
+                    countdown: *countdown,
+                };
+            }
             // State of game: waiting for next sentence, a second just passed.
             (State::Waiting { time_left, .. }, InGameMsg::UpdateTime) => {
                 *time_left = time_left.saturating_sub(Duration::from_secs(1));
@@ -331,25 +631,64 @@ impl Game {
             // State of game: waiting for timer to launch next sentence
             //
             // This will drop the timer and the interval, cancelling them.
-            (State::Waiting { timer, .. }, InGameMsg::Pause) => {
+            (State::Waiting { last, timer, .. }, InGameMsg::Pause) => {
                 self.state = State::WaitingPaused {
+                    last: *last,
                     time_left: timer.stop(),
                 };
             },
+            // State of game: the last sentence was drawn by mistake, put it back and go
+            // back to before it was drawn.
+            (State::Waiting { .. } | State::WaitingPaused { .. }, InGameMsg::Undo) => {
+                let paused = matches!(self.state, State::WaitingPaused { .. });
+
+                if let Some(drawn) = self.history.undo() {
+                    self.sentences.put_back(drawn.sentence);
+
+                    self.state = match (self.history.last(), drawn.countdown) {
+                        (Some(last), Some(time_left)) => {
+                            // The countdown ran out, start it again.
+                            let time_left = if time_left.is_zero() { self.duration } else { time_left };
+                            if paused {
+                                State::WaitingPaused { last, time_left }
+                            } else {
+                                waiting_state(ctx.link(), last, time_left)
+                            }
+                        }
+                        // It was the first sentence of the game.
+                        _ => State::GettingSoundPermission,
+                    };
+                }
+            }
             // State of game: resume in playing mode
-            (State::PlayingPaused { current }, InGameMsg::Resume) => {
+            (State::PlayingPaused { current, countdown }, InGameMsg::Resume) => {
                 self.state = State::Playing {
                     current: *current,
+                    countdown: *countdown,
                 };
             }
             // State of game: resume in waiting mode
-            (State::WaitingPaused { time_left }, InGameMsg::Resume) => {
-                self.state = waiting_state(ctx.link(), *time_left);
+            (State::WaitingPaused { last, time_left }, InGameMsg::Resume) => {
+                self.state = waiting_state(ctx.link(), *last, *time_left);
             }
             // State of game: received a go home event
             (State::PlayingPaused { .. } | State::WaitingPaused { .. } | State::Finished, InGameMsg::GoHome) => {
-                self.state = State::SelectingFamilies { families: Default::default() };
-                self.sentences = Sentences::new(&Default::default());
+                SavedGame::clear();
+                self.state = State::SelectingFamilies { selection: Default::default(), expanded: Default::default() };
+                self.sentences = Sentences::new(&Default::default(), 0, self.strategy);
+                self.history.clear();
+                self.bingo.clear();
+            }
+            // State of game: the bingo grids are ready, the caller draws the elements they were made with.
+            (State::Mode { selection, .. }, InGameMsg::LaunchBingo(grids)) => {
+                self.sentences = Sentences::new(selection, Sentences::random_seed(), self.strategy);
+                self.history.clear();
+                self.bingo = grids;
+                self.state = State::GettingSoundPermission;
+            }
+            // State of game: another mode is done, keep what it was played with.
+            (State::Mode { selection, .. }, InGameMsg::GoHome) => {
+                self.state = State::SelectingFamilies { selection: std::mem::take(selection), expanded: Default::default() };
             }
             _ => (),
         }
@@ -358,44 +697,153 @@ impl Game {
     }
 
     fn update_before_game(&mut self, msg: BeforeGameMsg) -> bool {
-        let families = match &mut self.state {
-            State::SelectingFamilies { families } => families,
+        let (selection, expanded) = match &mut self.state {
+            State::SelectingFamilies {
+                selection,
+                expanded,
+            } => (selection, expanded),
             _ => return false,
         };
 
         match msg {
             BeforeGameMsg::Toggle(f) => {
-                if families.contains(&f) {
-                    families.remove(&f);
+                if Sentence::all_in(f).all(|st| selection.contains(&st)) {
+                    Sentence::all_in(f).for_each(|st| {
+                        selection.remove(&st);
+                    });
                 } else {
-                    families.insert(f);
+                    selection.extend(Sentence::all_in(f));
+                }
+            }
+            BeforeGameMsg::ToggleElement(st) => {
+                if !selection.remove(&st) {
+                    selection.insert(st);
+                }
+            }
+            BeforeGameMsg::ToggleExpanded(f) => {
+                if !expanded.remove(&f) {
+                    expanded.insert(f);
                 }
             }
-            BeforeGameMsg::SelectAllFamilies => families.extend(Family::into_enum_iter()),
-            BeforeGameMsg::ClearAllFamilies => families.clear(),
+            BeforeGameMsg::SelectAllFamilies => {
+                selection.extend(Family::all().flat_map(Sentence::all_in))
+            }
+            BeforeGameMsg::ClearAllFamilies => selection.clear(),
             BeforeGameMsg::LaunchGame => {
-                self.sentences = Sentences::new(families);
+                self.sentences = Sentences::new(selection, Sentences::random_seed(), self.strategy);
+                self.history.clear();
+                self.bingo.clear();
                 self.state = State::GettingSoundPermission;
             }
+            BeforeGameMsg::LaunchMode(mode) => {
+                self.state = State::Mode {
+                    mode,
+                    selection: std::mem::take(selection),
+                };
+            }
+            BeforeGameMsg::ResumeGame => {
+                let saved = match self.saved.take() {
+                    Some(saved) => saved,
+                    None => return false,
+                };
+
+                self.duration = saved.duration;
+                self.strategy = saved.strategy;
+                self.phonetics = saved.phonetics;
+                self.sentences = saved.sentences;
+                self.history = saved.history;
+                self.bingo = saved.bingo;
+                // Timers cannot be saved, and sound needs a user interaction first:
+                // the game always starts again paused.
+                self.state = match saved.state {
+                    SavedState::Settings => State::GettingSoundPermission,
+                    SavedState::Playing { current, countdown } => {
+                        State::PlayingPaused { current, countdown }
+                    }
+                    SavedState::Waiting { last, time_left } => {
+                        State::WaitingPaused { last, time_left }
+                    }
+                };
+            }
         }
 
         true
     }
+
+    /// Save the game in the browser after each transition, to resume it if the
+    /// page is reloaded.
+    ///
+    /// A finished game is forgotten, outside of a game (selecting families, other
+    /// modes) the last save is kept.
+    fn save(&mut self) {
+        let state = match self.state {
+            State::GettingSoundPermission => SavedState::Settings,
+            State::Playing { current, countdown } | State::PlayingPaused { current, countdown } => {
+                SavedState::Playing { current, countdown }
+            }
+            State::Waiting {
+                last, time_left, ..
+            }
+            | State::WaitingPaused { last, time_left } => SavedState::Waiting { last, time_left },
+            State::Finished => return SavedGame::clear(),
+            _ => return,
+        };
+
+        // Another game was started, the one saved before is gone.
+        self.saved = None;
+        SavedGame {
+            duration: self.duration,
+            strategy: self.strategy,
+            phonetics: self.phonetics,
+            sentences: self.sentences.clone(),
+            history: self.history.clone(),
+            bingo: self.bingo.clone(),
+            state,
+        }
+        .save();
+    }
 }
 
 /// Make all the families available for selection/deselection.
-fn family_view(link: &Scope<Game>, families: &HashSet<Family>) -> Html {
+///
+/// [Expanded][BeforeGameMsg::ToggleExpanded] families also show their elements
+/// for individual selection.
+fn family_view(
+    link: &Scope<Game>,
+    selection: &HashSet<Sentence>,
+    expanded: &HashSet<Family>,
+) -> Html {
+    let family = |f: Family| {
+        let selected = Sentence::all_in(f)
+            .filter(|st| selection.contains(st))
+            .count();
+        let total = Sentence::all_in(f).count();
+        let onclick = link.callback(move |_| BeforeGameMsg::ToggleExpanded(f));
+
+        html! {
+            <div>
+                { f.render(link, selected == total) }
+                <button {onclick} class={ style::button_expand_family() }>
+                    { format!("Choisir les éléments ({selected}/{total})") }
+                </button>
+                if expanded.contains(&f) {
+                    { for Sentence::all_in(f).map(|st| st.render(link, selection.contains(&st))) }
+                }
+            </div>
+        }
+    };
+
     html! {
         <div>
-            { for Family::into_enum_iter().map(|f| f.render(link, families.contains(&f))) }
+            { for Family::all().map(family) }
         </div>
     }
 }
 
-/// The start button is only shown if at least one family has been selected
-/// to play.
-fn start_button(link: &Scope<Game>, families: &HashSet<Family>) -> Html {
-    if !families.is_empty() {
+/// The start buttons, for the caller and the [other modes][Mode], are only
+/// shown if at least one element has been selected to play.
+fn start_button(link: &Scope<Game>, selection: &HashSet<Sentence>) -> Html {
+    if !selection.is_empty() {
         let onclick = link.callback(|_| BeforeGameMsg::LaunchGame);
 
         html! {
@@ -404,6 +852,11 @@ fn start_button(link: &Scope<Game>, families: &HashSet<Family>) -> Html {
                 <button {onclick}>
                     { "Jouer" }
                 </button>
+                { for Mode::ALL.into_iter().filter(|mode| mode.is_playable(selection)).map(|mode| html! {
+                    <button onclick={ link.callback(move |_| BeforeGameMsg::LaunchMode(mode)) }>
+                        { mode.label() }
+                    </button>
+                }) }
             </div>
         }
     } else {
@@ -411,14 +864,80 @@ fn start_button(link: &Scope<Game>, families: &HashSet<Family>) -> Html {
     }
 }
 
+/// Component for another [mode][Mode], going back [home][InGameMsg::GoHome] when
+/// it is done.
+fn mode_view(link: &Scope<Game>, mode: Mode, selection: &HashSet<Sentence>) -> Html {
+    let on_exit = link.callback(|()| InGameMsg::GoHome);
+    let mut elements: Vec<_> = selection.iter().copied().collect();
+    elements.sort_unstable();
+    let mut families: Vec<_> = elements.iter().map(|st| st.family()).collect();
+    families.dedup();
+
+    match mode {
+        Mode::HotSeat => {
+            let props = HotSeatProps { families, on_exit };
+            html! { <HotSeat ..props /> }
+        }
+        Mode::Solo => {
+            let props = SoloProps { families, on_exit };
+            html! { <Solo ..props /> }
+        }
+        Mode::WhichFamily => {
+            let props = WhichFamilyProps {
+                selection: elements,
+                on_exit,
+            };
+            html! { <WhichFamily ..props /> }
+        }
+        Mode::Dictation => {
+            let props = DictationProps {
+                selection: elements,
+                on_exit,
+            };
+            html! { <Dictation ..props /> }
+        }
+        Mode::PictureMatch => {
+            let props = PictureMatchProps {
+                selection: elements,
+                on_exit,
+            };
+            html! { <PictureMatch ..props /> }
+        }
+        Mode::OddOneOut => {
+            let props = OddOneOutProps {
+                selection: elements,
+                on_exit,
+            };
+            html! { <OddOneOut ..props /> }
+        }
+        Mode::Bingo => {
+            let props = BingoCardsProps {
+                selection: elements,
+                on_call: link.callback(InGameMsg::LaunchBingo),
+                on_exit,
+            };
+            html! { <BingoCards ..props /> }
+        }
+        Mode::Revision => {
+            let props = RevisionProps { on_exit };
+            html! { <Revision ..props /> }
+        }
+        Mode::Glossary => {
+            let props = GlossaryProps { on_exit };
+            html! { <Glossary ..props /> }
+        }
+    }
+}
+
 /// Produce a [`State::Waiting`] instance filled correctly with the
 /// time left for the [`Timer`] to the next sentence and sending the
 /// [`InGameMsg::UpdateTime`] every second for the countdown display.
 ///
 /// Used on [`InGameMsg::Resume`] and when the [`SentenceState::Element`] sound
 /// finishes and the countdown to the next sentence must be launched.
-fn waiting_state(link: &Scope<Game>, time_left: Duration) -> State {
+fn waiting_state(link: &Scope<Game>, last: Sentence, time_left: Duration) -> State {
     State::Waiting {
+        last,
         time_left,
         timer: {
             let link = link.clone();
@@ -426,7 +945,7 @@ fn waiting_state(link: &Scope<Game>, time_left: Duration) -> State {
                 link.send_message(InGameMsg::NextSentence)
             })
         },
-        seconds: {
+        _seconds: {
             let link = link.clone();
             Interval::new(
                 1_000, /* ms */
@@ -436,6 +955,64 @@ fn waiting_state(link: &Scope<Game>, time_left: Duration) -> State {
     }
 }
 
+/// Transcript of a sentence: "Dans la famille <Family>, je voudrais <element>".
+///
+/// The family is shown as soon as its sound starts, the element only once it is
+/// its turn to be said, so that the text follows the audio. The element then
+/// comes with its [details][Sentence::render_details()], and its
+/// [pronunciation][Sentence::render_pronunciation()] when `phonetics` is set.
+fn transcript(sentence: Sentence, state: SentenceState, phonetics: bool) -> Html {
+    let family = sentence.family();
+    let element = match state {
+        SentenceState::Family => "...",
+        SentenceState::Element => sentence.label(),
+    };
+
+    html! {
+        <>
+            <p>
+                { "Dans la famille " }
+                <strong style={ format!("border-bottom:3px solid {};", family.color()) }>{ family.to_string() }</strong>
+                { ", je voudrais " }
+                <strong>{ element }</strong>
+            </p>
+            if matches!(state, SentenceState::Element) {
+                if phonetics {
+                    { sentence.render_pronunciation() }
+                }
+                { sentence.render_details() }
+            }
+        </>
+    }
+}
+
+/// Number of sentences said so far, and whether the family of the last one
+/// has been completely called.
+fn progress(sentences: &Sentences, last: Sentence) -> Html {
+    let family = last.family();
+
+    html! {
+        <>
+            <p> { format!("Phrase {} sur {}", sentences.drawn(), sentences.total()) } </p>
+            if sentences.is_family_done(family) {
+                <p> { format!("Tous les éléments choisis de la famille {family} ont été appelés !") } </p>
+            }
+        </>
+    }
+}
+
+/// Illustration of the element, if it has one.
+///
+/// Like the [transcript], it is only shown once the element is being said.
+fn element_image(sentence: Sentence, state: SentenceState) -> Html {
+    match (state, sentence.image_file()) {
+        (SentenceState::Element, Some(src)) => html! {
+            <img {src} alt={ sentence.label() } class={ style::element_image() } />
+        },
+        _ => html! {},
+    }
+}
+
 /// Slider to select the duration of the next countdown to the next sentence.
 fn timer_slider(link: &Scope<Game>, current_duration: Duration) -> Html {
     html! {
@@ -461,11 +1038,111 @@ fn timer_slider(link: &Scope<Game>, current_duration: Duration) -> Html {
     }
 }
 
+/// Checkbox to show the pronunciation of the elements during the game.
+fn phonetics_checkbox(link: &Scope<Game>, phonetics: bool) -> Html {
+    html! {
+        <p>
+            <input
+                id="phonetics"
+                type="checkbox"
+                checked={ phonetics }
+                onchange={ link.callback(|_| InGameMsg::TogglePhonetics) }
+            />
+            <label for="phonetics"> { "Afficher les syllabes et la prononciation (API) des éléments" } </label>
+        </p>
+    }
+}
+
+/// Select how the sentences are drawn.
+///
+/// [`DrawStrategy::WithReplacement`] also shows an input for its number of draws,
+/// which starts at the number of selected elements.
+fn strategy_select(link: &Scope<Game>, current: DrawStrategy, selected: usize) -> Html {
+    let rounds = match current {
+        DrawStrategy::WithReplacement { rounds } => rounds,
+        _ => selected,
+    };
+    let strategies = DrawStrategy::all(rounds);
+
+    html! {
+        <>
+            <select
+                onchange={
+                    link.batch_callback(move |e: Event| {
+                        // Unchecked: we define the callback inside the element it concerns, we cannot
+                        // be referencing the wrong one.
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let index = usize::try_from(select.selected_index()).ok()?;
+                        strategies.get(index).map(|&st| Msg::from(InGameMsg::ChangeStrategy(st)))
+                    })
+                }
+            >
+                { for strategies.iter().map(|st| html! {
+                    <option selected={ *st == current }> { st.label() } </option>
+                }) }
+            </select>
+            if let DrawStrategy::WithReplacement { rounds } = current {
+                <label for="rounds"> { "Nombre de tirages" } </label>
+                <input
+                    id="rounds"
+                    type="number"
+                    min="1"
+                    max={ MAX_DRAW_ROUNDS.to_string() }
+                    value={ rounds.to_string() }
+                    onchange={
+                        link.callback(|e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            let rounds = input.value_as_number().round().clamp(1.0, MAX_DRAW_ROUNDS as _) as usize;
+                            InGameMsg::ChangeStrategy(DrawStrategy::WithReplacement { rounds })
+                        })
+                    }
+                />
+            }
+        </>
+    }
+}
+
+/// Input to read and change the seed used to shuffle the sentences.
+///
+/// Sharing it lets another classroom (or a bug report) get the exact same
+/// order, given the same selection.
+fn seed_input(link: &Scope<Game>, seed: u64) -> Html {
+    html! {
+        <>
+            <label for="seed"> { "Graine du tirage (même graine et même sélection = même ordre)" } </label>
+            <input
+                id="seed"
+                type="text"
+                inputmode="numeric"
+                value={ seed.to_string() }
+                onchange={
+                    link.batch_callback(|e: Event| {
+                        // Unchecked: we define the callback inside the element it concerns, we cannot
+                        // be referencing the wrong one.
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        input.value().trim().parse().ok().map(|seed| Msg::from(InGameMsg::ChangeSeed(seed)))
+                    })
+                }
+            />
+        </>
+    }
+}
+
 /// Button to click on to [pause][InGameMsg::Pause] the game.
 fn pause_button(link: &Scope<Game>) -> Html {
     html! { <button onclick={ link.callback(|_| InGameMsg::Pause) }> { "Pause" } </button> }
 }
 
+/// Button to click on to [say the last sentence again][InGameMsg::Replay].
+fn replay_button(link: &Scope<Game>) -> Html {
+    html! { <button onclick={ link.callback(|_| InGameMsg::Replay) }> { "Répéter la phrase" } </button> }
+}
+
+/// Button to click on to [put the last sentence back][InGameMsg::Undo].
+fn undo_button(link: &Scope<Game>) -> Html {
+    html! { <button onclick={ link.callback(|_| InGameMsg::Undo) }> { "Annuler la dernière phrase" } </button> }
+}
+
 /// Button to click on to [skip to the next sentence][InGameMsg::NextSentence].
 fn next_sentence_button(link: &Scope<Game>) -> Html {
     html! { <button onclick={ link.callback(|_| InGameMsg::NextSentence) }> { "Phrase suivante" } </button> }
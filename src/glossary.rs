@@ -0,0 +1,107 @@
+//! Every family and element of the deck, to browse the vocabulary.
+//!
+//! See [`Glossary`].
+use yew::prelude::*;
+
+use crate::audio::Playlist;
+use crate::family::Family;
+use crate::sentences::Sentence;
+use crate::style;
+
+/// Glossary component: every family with its logo and color, every element with
+/// its details, its illustration and buttons to hear it alone or in its whole
+/// sentence.
+pub struct Glossary {
+    /// Says the elements and sentences.
+    playlist: Playlist,
+}
+
+#[derive(Debug)]
+pub enum GlossaryMsg {
+    /// Say the element alone.
+    PlayElement(Sentence),
+    /// Say the whole sentence: "Dans la famille ..., je voudrais ...".
+    PlaySentence(Sentence),
+    /// A sound of the playlist just ended.
+    SoundEnded,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct GlossaryProps {
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for Glossary {
+    type Message = GlossaryMsg;
+    type Properties = GlossaryProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let playlist = Playlist::new(move |_| link.send_message(GlossaryMsg::SoundEnded));
+
+        Self { playlist }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            GlossaryMsg::PlayElement(sentence) => self.playlist.play([sentence.element_sound()]),
+            GlossaryMsg::PlaySentence(sentence) => self.playlist.play(sentence.sounds()),
+            GlossaryMsg::SoundEnded => {
+                self.playlist.play_next();
+            }
+            GlossaryMsg::Exit => {
+                self.playlist.stop();
+                ctx.props().on_exit.emit(());
+            }
+        }
+
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let exit = html! {
+            <button onclick={ link.callback(|_| GlossaryMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+        };
+
+        let element = |st: Sentence| {
+            html! {
+                <li>
+                    <p> { st.label() } </p>
+                    { st.render_details() }
+                    if let Some(image) = st.image_file() {
+                        <img src={ image } alt={ st.label() } class={ style::glossary_image() } />
+                    }
+                    <button onclick={ link.callback(move |_| GlossaryMsg::PlayElement(st)) }> { "Écouter le mot" } </button>
+                    <button onclick={ link.callback(move |_| GlossaryMsg::PlaySentence(st)) }> { "Écouter la phrase" } </button>
+                </li>
+            }
+        };
+        let family = |f: Family| {
+            html! {
+                <section style={ format!("border:3px solid {};margin-bottom:2%;", f.color()) }>
+                    <h2 style={ format!("border-bottom:3px solid {};", f.color()) }>
+                        <img src={ f.logo_file() } alt={ format!("Logo de la famille {f}") } style={ f.logo_style(false).to_string() } />
+                        { f.to_string() }
+                    </h2>
+                    <ul style="list-style:none;">
+                        { for Sentence::all_in(f).map(element) }
+                    </ul>
+                </section>
+            }
+        };
+
+        html! {
+            <>
+                { exit.clone() }
+                <hr />
+                { for Family::all().map(family) }
+                <hr />
+                { exit }
+            </>
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Sentences already said during a game.
+//!
+//! See [`History`].
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::family::Family;
+use crate::sentences::Sentence;
+
+/// Ordered list of the sentences drawn during a game.
+///
+/// Used by the teacher to check that a student really completed a family.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct History(Vec<Drawn>);
+
+/// A sentence and when it was drawn.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Drawn {
+    /// The sentence that was drawn.
+    pub sentence: Sentence,
+    /// When it was drawn, in milliseconds since the UNIX epoch.
+    pub at: f64,
+    /// What was left of the countdown to the next sentence when it was drawn,
+    /// `None` for the first sentence of the game.
+    ///
+    /// Restored if the sentence is [undone][History::undo()].
+    pub countdown: Option<Duration>,
+}
+
+impl History {
+    /// Record a newly drawn sentence.
+    pub fn push(&mut self, sentence: Sentence, countdown: Option<Duration>) {
+        self.0.push(Drawn {
+            sentence,
+            at: js_sys::Date::now(),
+            countdown,
+        });
+    }
+
+    /// Forget the last drawn sentence and return it.
+    pub fn undo(&mut self) -> Option<Drawn> {
+        self.0.pop()
+    }
+
+    /// The last drawn sentence.
+    pub fn last(&self) -> Option<Sentence> {
+        self.0.last().map(|drawn| drawn.sentence)
+    }
+
+    /// The drawn sentences, in order.
+    pub fn sentences(&self) -> impl Iterator<Item = Sentence> + '_ {
+        self.0.iter().map(|drawn| drawn.sentence)
+    }
+
+    /// Forget everything, for a new game.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Collapsible list of the sentences drawn so far, in order, shown during the game.
+    pub fn render_list(&self) -> Html {
+        if self.0.is_empty() {
+            return html! {};
+        }
+
+        html! {
+            <details>
+                <summary> { format!("Phrases déjà appelées ({})", self.0.len()) } </summary>
+                <ol>
+                    { for self.0.iter().map(|drawn| html! {
+                        <li> { format!("{} : {}", drawn.sentence.family(), drawn.sentence.label()) } </li>
+                    }) }
+                </ol>
+            </details>
+        }
+    }
+
+    /// Every sentence drawn during the game, grouped by family, with the time
+    /// they were drawn at and their position in the game.
+    pub fn render_recap(&self) -> Html {
+        let mut families = BTreeMap::<Family, Vec<(usize, &Drawn)>>::new();
+        for (index, drawn) in self.0.iter().enumerate() {
+            families
+                .entry(drawn.sentence.family())
+                .or_default()
+                .push((index + 1, drawn));
+        }
+
+        let family = |(family, drawn): (Family, Vec<(usize, &Drawn)>)| {
+            html! {
+                <>
+                    <h3 style={ format!("border-bottom:3px solid {};", family.color()) }> { family.to_string() } </h3>
+                    <ul>
+                        { for drawn.into_iter().map(|(index, drawn)| html! {
+                            <li>
+                                { format!("n°{index} {} ({})", drawn.sentence.with_article(), format_time(drawn.at)) }
+                                if let Some(definition) = drawn.sentence.definition() {
+                                    { " : " }{ definition }
+                                }
+                            </li>
+                        }) }
+                    </ul>
+                </>
+            }
+        };
+
+        html! {
+            <div>
+                <h2> { format!("Récapitulatif des {} phrases appelées", self.0.len()) } </h2>
+                { for families.into_iter().map(family) }
+            </div>
+        }
+    }
+}
+
+/// Local time of day for a timestamp in milliseconds since the UNIX epoch.
+fn format_time(at: f64) -> String {
+    js_sys::Date::new(&at.into())
+        .to_locale_time_string("fr-FR")
+        .into()
+}
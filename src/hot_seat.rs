@@ -0,0 +1,371 @@
+//! Seven families card game with several players on the same device.
+//!
+//! See [`HotSeat`].
+use rand::rngs::OsRng;
+use yew::prelude::*;
+
+use crate::audio::Playlist;
+use crate::card_game::{
+    Ask, AskOutcome, AskResult, CardGame, SetupError, MAX_PLAYERS, MIN_PLAYERS,
+};
+use crate::family::Family;
+use crate::sentences::Sentence;
+use crate::style;
+
+/// Card game component, played "hot seat": players take turns on the same
+/// tablet, each hand hidden behind a "pass the device" screen.
+///
+/// Each card asked for is said aloud with the family and element sounds, to keep
+/// the oral practice of the [caller][crate::game::Game].
+pub struct HotSeat {
+    /// Says the cards asked for.
+    playlist: Playlist,
+    /// Number of players for the next game.
+    players: usize,
+    /// Game in progress, if any.
+    game: Option<CardGame>,
+    /// Why the last game could not be set up.
+    error: Option<SetupError>,
+    /// What is shown to players.
+    stage: Stage,
+}
+
+/// What is shown to players, in the order of a turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    /// Choosing the number of players.
+    Setup,
+    /// Hiding the hands while the device goes to the next player.
+    PassDevice,
+    /// The current player looks at their hand and chooses what to ask.
+    Hand {
+        /// Player chosen to be asked, the card comes next.
+        target: Option<usize>,
+    },
+    /// What the last ask gave.
+    Result { ask: Ask, result: AskResult },
+    /// All the families are down.
+    Over,
+}
+
+#[derive(Debug)]
+pub enum HotSeatMsg {
+    /// Change the number of players for the next game.
+    SetPlayers(usize),
+    /// Deal the cards.
+    Start,
+    /// The current player has the device, show their hand.
+    ShowHand,
+    /// Choose the player to ask.
+    SelectTarget(usize),
+    /// Ask the selected player for a card.
+    Ask(Sentence),
+    /// Go on after the result of an ask.
+    Continue,
+    /// A sound of the playlist just ended.
+    SoundEnded,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct HotSeatProps {
+    /// Families to play with.
+    pub families: Vec<Family>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for HotSeat {
+    type Message = HotSeatMsg;
+    type Properties = HotSeatProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let playlist = Playlist::new(move |_| link.send_message(HotSeatMsg::SoundEnded));
+
+        Self {
+            playlist,
+            players: MIN_PLAYERS,
+            game: None,
+            error: None,
+            stage: Stage::Setup,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match (self.stage, msg) {
+            (Stage::Setup, HotSeatMsg::SetPlayers(players)) => {
+                self.players = players.clamp(MIN_PLAYERS, MAX_PLAYERS);
+            }
+            (Stage::Setup, HotSeatMsg::Start) => {
+                let families = ctx.props().families.iter().copied().collect();
+                match CardGame::new(&families, self.players, &mut OsRng) {
+                    Ok(game) => {
+                        self.stage = if game.is_over() {
+                            Stage::Over
+                        } else {
+                            Stage::PassDevice
+                        };
+                        self.game = Some(game);
+                        self.error = None;
+                    }
+                    Err(err) => self.error = Some(err),
+                }
+            }
+            (Stage::PassDevice, HotSeatMsg::ShowHand) => {
+                self.stage = Stage::Hand { target: None };
+            }
+            (Stage::Hand { .. }, HotSeatMsg::SelectTarget(target)) => {
+                self.stage = Stage::Hand {
+                    target: Some(target),
+                };
+            }
+            (
+                Stage::Hand {
+                    target: Some(target),
+                },
+                HotSeatMsg::Ask(card),
+            ) => {
+                let game = match &mut self.game {
+                    Some(game) => game,
+                    None => return false,
+                };
+                let ask = Ask {
+                    asker: game.current_player(),
+                    target,
+                    card,
+                };
+                if let Ok(result) = game.ask(ask) {
+                    self.playlist.play(card.sounds());
+                    self.stage = Stage::Result { ask, result };
+                }
+            }
+            (Stage::Result { ask, .. }, HotSeatMsg::Continue) => {
+                self.playlist.stop();
+                self.stage = match &self.game {
+                    Some(game) if game.is_over() => Stage::Over,
+                    // Same player, no need to hide their hand.
+                    Some(game) if game.current_player() == ask.asker => {
+                        Stage::Hand { target: None }
+                    }
+                    _ => Stage::PassDevice,
+                };
+            }
+            (_, HotSeatMsg::SoundEnded) => {
+                self.playlist.play_next();
+                return false;
+            }
+            (_, HotSeatMsg::Exit) => {
+                self.playlist.stop();
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let exit = html! {
+            <>
+                <hr />
+                <button onclick={ link.callback(|_| HotSeatMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+            </>
+        };
+
+        let game = match (&self.game, self.stage) {
+            (_, Stage::Setup) | (None, _) => {
+                return html! {
+                    <>
+                        { players_select(ctx, self.players) }
+                        if let Some(err) = self.error {
+                            <p> { setup_error(err) } </p>
+                        }
+                        <button onclick={ link.callback(|_| HotSeatMsg::Start) }> { "Distribuer les cartes" } </button>
+                        { exit }
+                    </>
+                };
+            }
+            (Some(game), _) => game,
+        };
+
+        let content = match self.stage {
+            Stage::Setup => html! {},
+            Stage::PassDevice => html! {
+                <>
+                    <p> { format!("Passez l'appareil à {}", player_name(game.current_player())) } </p>
+                    <button onclick={ link.callback(|_| HotSeatMsg::ShowHand) }>
+                        { format!("Je suis {}, voir ma main", player_name(game.current_player())) }
+                    </button>
+                </>
+            },
+            Stage::Hand { target } => hand_view(
+                game,
+                player_name,
+                target,
+                link.callback(HotSeatMsg::SelectTarget),
+                link.callback(HotSeatMsg::Ask),
+            ),
+            Stage::Result { ask, result } => html! {
+                <>
+                    <p> { ask_text(ask, player_name) } </p>
+                    <p> { result_text(ask, result, player_name, true) } </p>
+                    <button onclick={ link.callback(|_| HotSeatMsg::Continue) }> { "Continuer" } </button>
+                </>
+            },
+            Stage::Over => html! {
+                <>
+                    <p> { "Partie terminée !" } </p>
+                    <p> { winners_text(game, player_name) } </p>
+                </>
+            },
+        };
+
+        html! {
+            <>
+                { content }
+                <hr />
+                { scores_view(game, player_name) }
+                { exit }
+            </>
+        }
+    }
+}
+
+/// Name of a player, as shown to players.
+fn player_name(player: usize) -> String {
+    format!("Joueur {}", player + 1)
+}
+
+/// The sentence said for an ask, players being called by `name`.
+pub fn ask_text(ask: Ask, name: fn(usize) -> String) -> String {
+    format!(
+        "{} à {} : « Dans la famille {}, je voudrais {} »",
+        name(ask.asker),
+        name(ask.target),
+        ask.card.family(),
+        ask.card.label()
+    )
+}
+
+/// What happened after an ask, players being called by `name`.
+///
+/// The card drawn is only told when `show_drawn` is set, it must stay hidden from
+/// the other players when they can see the screen.
+pub fn result_text(
+    ask: Ask,
+    result: AskResult,
+    name: fn(usize) -> String,
+    show_drawn: bool,
+) -> String {
+    let outcome = match result.outcome {
+        AskOutcome::Given => format!("{} donne la carte, rejouez !", name(ask.target)),
+        AskOutcome::LuckyDraw => "Bonne pioche ! Rejouez !".to_owned(),
+        AskOutcome::Drew(card) if show_drawn => {
+            format!("Pioche : {} ({})", card.label(), card.family())
+        }
+        AskOutcome::Drew(_) => format!("{} pioche une carte.", name(ask.asker)),
+        AskOutcome::EmptyPile => "Pioche vide !".to_owned(),
+    };
+
+    match result.completed {
+        Some(family) => format!("{outcome} Famille {family} complète !"),
+        None => outcome,
+    }
+}
+
+/// Who won the game, players being called by `name`.
+pub fn winners_text(game: &CardGame, name: fn(usize) -> String) -> String {
+    let winners: Vec<_> = game.winners().into_iter().map(name).collect();
+    format!("Gagnant(s) : {}", winners.join(", "))
+}
+
+/// Select the number of players.
+fn players_select(ctx: &Context<HotSeat>, current: usize) -> Html {
+    html! {
+        <>
+            <p> { "Nombre de joueurs" } </p>
+            { for (MIN_PLAYERS..=MAX_PLAYERS).map(|players| html! {
+                <button
+                    onclick={ ctx.link().callback(move |_| HotSeatMsg::SetPlayers(players)) }
+                    class={ style::button_select_family(players == current) }
+                >
+                    { players }
+                </button>
+            }) }
+        </>
+    }
+}
+
+/// Why a game could not be set up, as shown to players.
+pub fn setup_error(err: SetupError) -> String {
+    match err {
+        SetupError::PlayerCount(players) => {
+            format!("{players} joueurs, il en faut entre {MIN_PLAYERS} et {MAX_PLAYERS}")
+        }
+        SetupError::NotEnoughCards => {
+            "Pas assez de cartes pour tous les joueurs, choisissez plus de familles".to_owned()
+        }
+    }
+}
+
+/// Hand of the current player, with the players they can ask and, once one is
+/// chosen, the cards they can ask for.
+///
+/// Players are called by `name`, `on_target` and `on_ask` are called when a player
+/// then a card are chosen.
+pub fn hand_view(
+    game: &CardGame,
+    name: fn(usize) -> String,
+    target: Option<usize>,
+    on_target: Callback<usize>,
+    on_ask: Callback<Sentence>,
+) -> Html {
+    let player = game.current_player();
+
+    html! {
+        <>
+            <p> { format!("Main de {}", name(player)) } </p>
+            { for game.hand(player).iter().map(|card| card.render_card(None)) }
+            <hr />
+            <p> { "Demander à" } </p>
+            { for (0..game.players()).filter(|&p| p != player).map(|p| html! {
+                <button
+                    onclick={ on_target.reform(move |_| p) }
+                    class={ style::button_select_family(Some(p) == target) }
+                >
+                    { format!("{} ({} cartes)", name(p), game.hand(p).len()) }
+                </button>
+            }) }
+            if target.is_some() {
+                <p> { "La carte" } </p>
+                { for game.askable_cards(player).into_iter().map(|card| {
+                    card.render_card(Some(on_ask.reform(move |_| card)))
+                }) }
+            }
+        </>
+    }
+}
+
+/// Families put down by each player and cards left in the pile, players being
+/// called by `name`.
+pub fn scores_view(game: &CardGame, name: fn(usize) -> String) -> Html {
+    html! {
+        <>
+            <p> { format!("Pioche : {} cartes", game.pile_len()) } </p>
+            <ul>
+                { for (0..game.players()).map(|p| html! {
+                    <li>
+                        { format!("{} : {} famille(s)", name(p), game.completed(p).len()) }
+                        { for game.completed(p).iter().map(|f| html! {
+                            <img src={ f.logo_file() } alt={ f.to_string() } title={ f.to_string() } style={ f.logo_style(false).to_string() } />
+                        }) }
+                    </li>
+                }) }
+            </ul>
+        </>
+    }
+}
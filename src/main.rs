@@ -4,6 +4,7 @@
 //!    - [x] Choose at least one family, at most all of them
 //!    - [ ] Show family logos and colors
 //!    - [x] Button to (un)select all families
+//!    - [x] Choose single elements inside a family
 //!    - [x] Button to start game becomes visible after one family has been chosen
 //! - [x] Game itself
 //!    - [x] Button to get permission to play sound
@@ -17,14 +18,47 @@
 //!    - [x] Show nice buttons
 //! - [x] Finishing the game
 //!    - [x] All sentences have been said
+//!    - [x] Recap of the sentences said, by family
 //!    - [x] Go back to selecting families
 //!    - [x] Show nice buttons
+//! - [x] Other modes, with the selected elements
+//!    - [x] Seven families card game, several players on the same device
+//!    - [x] Seven families card game, alone against computer opponents
+//!    - [x] "Which family?" listening quiz, with a score and the mistakes
+//!    - [x] Dictation, tolerant of accents, case and hyphens if wanted
+//!    - [x] Matching the illustration of an element to its word
+//!    - [x] "L'intrus", finding the element from another family
+//!    - [x] Bingo, printable grids and checking the claims during the call
+//! - [x] Revision of the elements due today, with spaced repetition of the quiz answers
+//! - [x] Glossary of every family and element, with their sounds
+//! - [x] Article, gender, definition and example of the elements
+//! - [x] Syllables and IPA of the elements under the transcript, if wanted
+//! - [x] Game in progress saved in the browser, resumed paused after a reload
 mod audio;
+mod bingo;
+mod bingo_cards;
+mod bots;
+mod card_game;
+mod deck;
+mod dictation;
 mod family;
 mod game;
+mod glossary;
+mod history;
+mod hot_seat;
+mod manifest;
+mod odd_one_out;
+mod picture_match;
+mod revision;
+mod saved_game;
+mod score;
 mod sentences;
+mod solo;
+mod spelling;
+mod storage;
 mod style;
 mod timer;
+mod which_family;
 
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
@@ -0,0 +1,97 @@
+//! Raw deck manifest, as written in `assets/deck.toml`.
+//!
+//! Those types only mirror the file, see [`crate::deck`] for the checked
+//! version used by the game.
+use serde::Deserialize;
+
+/// Path to the manifest, relative to the root of the website.
+pub const MANIFEST_PATH: &str = "assets/deck.toml";
+
+/// Minimum number of elements in a family.
+pub const MIN_ELEMENTS: usize = 3;
+/// Maximum number of elements in a family.
+pub const MAX_ELEMENTS: usize = 12;
+
+/// Whole manifest: all the families of the game, in display order.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    #[serde(rename = "family")]
+    pub families: Vec<FamilyEntry>,
+}
+
+/// One family and all its elements.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FamilyEntry {
+    /// Name of the family folder in `assets/`.
+    pub id: String,
+    /// Name shown to players.
+    pub name: String,
+    /// CSS color of the family.
+    pub color: String,
+    /// Logo file, relative to the family folder.
+    pub logo: String,
+    /// Sound file for "Dans la famille ...", relative to the family folder.
+    ///
+    /// Without it, the family is read by the browser's speech synthesis.
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// Elements of the family, between [`MIN_ELEMENTS`] and [`MAX_ELEMENTS`].
+    #[serde(rename = "element")]
+    pub elements: Vec<ElementEntry>,
+}
+
+/// One element of a family.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ElementEntry {
+    /// Identifier of the element, unique inside its family.
+    pub id: String,
+    /// Name shown to players, with its accents.
+    pub label: String,
+    /// Sound file for "je voudrais ...", relative to the family folder.
+    ///
+    /// Without it, the element is read by the browser's speech synthesis.
+    #[serde(default)]
+    pub sound: Option<String>,
+    /// Optional illustration, relative to the family folder.
+    #[serde(default)]
+    pub image: Option<String>,
+    /// Grammatical gender, for nouns.
+    #[serde(default)]
+    pub gender: Option<Gender>,
+    /// Definite article, "le", "la" or "l'", for nouns.
+    #[serde(default)]
+    pub article: Option<String>,
+    /// Short definition, for the glossary and the recap.
+    #[serde(default)]
+    pub definition: Option<String>,
+    /// Example of the element used in a sentence.
+    #[serde(default)]
+    pub example: Option<String>,
+    /// Pronunciation in the International Phonetic Alphabet, without slashes.
+    #[serde(default)]
+    pub ipa: Option<String>,
+    /// Label split in syllables, separated by "·".
+    #[serde(default)]
+    pub syllables: Option<String>,
+}
+
+/// Grammatical gender of an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Gender {
+    Feminine,
+    Masculine,
+}
+
+impl Gender {
+    /// Nature of the word, as shown to players.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Feminine => "nom féminin",
+            Self::Masculine => "nom masculin",
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! "L'intrus": finding the element that does not belong.
+//!
+//! See [`OddOneOut`].
+use std::collections::{BTreeMap, HashSet};
+
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use yew::prelude::*;
+
+use crate::audio::Playlist;
+use crate::family::Family;
+use crate::score::{Mistake, Score};
+use crate::sentences::Sentence;
+use crate::style;
+
+/// Number of rounds in a game, less if there are not enough different sets.
+const ROUNDS: usize = 10;
+/// Random tries to find a new set before looking through all of them.
+const RANDOM_TRIES: usize = 50;
+
+/// Quiz component: four elements are said, three from one family and one from
+/// another, the student finds the intruder.
+pub struct OddOneOut {
+    /// Says the elements.
+    playlist: Playlist,
+    /// Makes the sets, remembers those already played.
+    generator: Generator,
+    /// Rounds played in this game.
+    played: usize,
+    /// Answers so far.
+    score: Score,
+    /// What is shown to the student.
+    stage: Stage,
+}
+
+/// Four elements to listen to, one of them is the intruder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Round {
+    /// The elements, in the order they are said.
+    elements: [Sentence; 4],
+    /// The one from another family.
+    intruder: Sentence,
+}
+
+/// What is shown to the student.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    /// Waiting for a click, browsers only play sounds after one.
+    Start,
+    /// The elements were said, waiting for the answer.
+    Question(Round),
+    /// The student answered, showing whether it was right.
+    Answered { round: Round, given: Sentence },
+    /// All the rounds were played.
+    Over,
+}
+
+#[derive(Debug)]
+pub enum OddOneOutMsg {
+    /// Go to the next round, or start the game.
+    Next,
+    /// Say all the elements again.
+    Replay,
+    /// Say a single element.
+    Play(Sentence),
+    /// The student chose the intruder.
+    Answer(Sentence),
+    /// A sound of the playlist just ended.
+    SoundEnded,
+    /// Play a new game, with sets not played yet.
+    Restart,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct OddOneOutProps {
+    /// Elements to make the sets with.
+    pub selection: Vec<Sentence>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for OddOneOut {
+    type Message = OddOneOutMsg;
+    type Properties = OddOneOutProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let playlist = Playlist::new(move |_| link.send_message(OddOneOutMsg::SoundEnded));
+
+        Self {
+            playlist,
+            generator: Generator::new(&ctx.props().selection),
+            played: 0,
+            score: Score::default(),
+            stage: Stage::Start,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match (self.stage, msg) {
+            (Stage::Start | Stage::Answered { .. }, OddOneOutMsg::Next) => {
+                let round = if self.played < ROUNDS {
+                    self.generator.next(&mut OsRng)
+                } else {
+                    None
+                };
+                self.stage = match round {
+                    Some(round) => {
+                        self.played += 1;
+                        self.playlist
+                            .play(round.elements.iter().map(|st| st.element_sound()));
+                        Stage::Question(round)
+                    }
+                    None => Stage::Over,
+                };
+            }
+            (Stage::Question(round), OddOneOutMsg::Replay) => {
+                self.playlist
+                    .play(round.elements.iter().map(|st| st.element_sound()));
+                return false;
+            }
+            (Stage::Question(_) | Stage::Answered { .. }, OddOneOutMsg::Play(element)) => {
+                self.playlist.play([element.element_sound()]);
+                return false;
+            }
+            (Stage::Question(round), OddOneOutMsg::Answer(given)) => {
+                self.playlist.stop();
+                if given == round.intruder {
+                    self.score.right(round.intruder);
+                } else {
+                    self.score.wrong(Mistake {
+                        sentence: round.intruder,
+                        expected: round.intruder.label().to_owned(),
+                        given: given.label().to_owned(),
+                    });
+                }
+                self.stage = Stage::Answered { round, given };
+            }
+            (_, OddOneOutMsg::SoundEnded) => {
+                self.playlist.play_next();
+                return false;
+            }
+            (Stage::Over, OddOneOutMsg::Restart) => {
+                self.played = 0;
+                self.score = Score::default();
+                self.stage = Stage::Start;
+            }
+            (_, OddOneOutMsg::Exit) => {
+                self.playlist.stop();
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let content = match self.stage {
+            Stage::Start if !self.generator.is_possible() => html! {
+                <p> { "Choisissez au moins 3 éléments d'une famille et 1 élément d'une autre famille." } </p>
+            },
+            Stage::Start => html! {
+                <>
+                    <p> { "Écoutez les quatre mots : trois sont de la même famille, trouvez l'intrus." } </p>
+                    <button onclick={ link.callback(|_| OddOneOutMsg::Next) }> { "Commencer" } </button>
+                </>
+            },
+            Stage::Question(round) => html! {
+                <>
+                    <button onclick={ link.callback(|_| OddOneOutMsg::Replay) }> { "Réécouter les quatre mots" } </button>
+                    { for round.elements.iter().enumerate().map(|(index, &element)| html! {
+                        <p>
+                            <button onclick={ link.callback(move |_| OddOneOutMsg::Play(element)) }>
+                                { format!("Écouter le mot {}", index + 1) }
+                            </button>
+                            <button onclick={ link.callback(move |_| OddOneOutMsg::Answer(element)) }>
+                                { "C'est l'intrus" }
+                            </button>
+                        </p>
+                    }) }
+                    <hr />
+                    { self.score.render() }
+                </>
+            },
+            Stage::Answered { round, given } => html! {
+                <>
+                    if given == round.intruder {
+                        <p> { format!("Bonne réponse ! {} est dans la famille {}.", given.label(), given.family()) } </p>
+                    } else {
+                        <p> { format!("Non, l'intrus était {}, de la famille {}.", round.intruder.label(), round.intruder.family()) } </p>
+                    }
+                    // The intruder is shown selected.
+                    { for round.elements.iter().map(|&element| html! {
+                        <p>
+                            <button
+                                onclick={ link.callback(move |_| OddOneOutMsg::Play(element)) }
+                                class={ style::button_select_family(element == round.intruder) }
+                                style={ element.family().button_style(element == round.intruder) }
+                            >
+                                { format!("{} ({})", element.label(), element.family()) }
+                            </button>
+                        </p>
+                    }) }
+                    <hr />
+                    { self.score.render() }
+                    <button onclick={ link.callback(|_| OddOneOutMsg::Next) }> { "Question suivante" } </button>
+                </>
+            },
+            Stage::Over => html! {
+                <>
+                    { self.score.render_recap() }
+                    if self.generator.is_exhausted() {
+                        <p> { "Toutes les combinaisons possibles ont été jouées." } </p>
+                    } else {
+                        <button onclick={ link.callback(|_| OddOneOutMsg::Restart) }> { "Rejouer" } </button>
+                    }
+                </>
+            },
+        };
+
+        html! {
+            <>
+                { content }
+                <hr />
+                <button onclick={ link.callback(|_| OddOneOutMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+            </>
+        }
+    }
+}
+
+/// Makes the sets of a round from the selection, never twice the same during a
+/// session.
+#[derive(Debug)]
+struct Generator {
+    /// Selected elements, by family.
+    families: BTreeMap<Family, Vec<Sentence>>,
+    /// Sets already played, sorted.
+    seen: HashSet<[Sentence; 4]>,
+    /// `true` once no new set can be found.
+    exhausted: bool,
+}
+
+impl Generator {
+    fn new(selection: &[Sentence]) -> Self {
+        let mut families = BTreeMap::<Family, Vec<Sentence>>::new();
+        for &st in selection {
+            families.entry(st.family()).or_default().push(st);
+        }
+        for elements in families.values_mut() {
+            // Only `rng` decides the sets.
+            elements.sort_unstable();
+        }
+
+        Self {
+            families,
+            seen: HashSet::new(),
+            exhausted: false,
+        }
+    }
+
+    /// `true` if the selection allows at least one set: three elements of a family
+    /// and one of another.
+    fn is_possible(&self) -> bool {
+        self.families.len() >= 2 && self.families.values().any(|elements| elements.len() >= 3)
+    }
+
+    /// `true` once every possible set has been played.
+    fn is_exhausted(&self) -> bool {
+        self.exhausted || !self.is_possible()
+    }
+
+    /// A set never played before, `None` if there is none left.
+    fn next(&mut self, rng: &mut impl Rng) -> Option<Round> {
+        if self.is_exhausted() {
+            return None;
+        }
+
+        let round = (0..RANDOM_TRIES)
+            .filter_map(|_| self.random(rng))
+            .find(|round| !self.seen.contains(&sorted(round.elements)))
+            .or_else(|| self.first_unseen(rng));
+        match round {
+            Some(round) => {
+                self.seen.insert(sorted(round.elements));
+                Some(round)
+            }
+            None => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+
+    /// A random set, maybe already played.
+    fn random(&self, rng: &mut impl Rng) -> Option<Round> {
+        let families: Vec<_> = self
+            .families
+            .iter()
+            .filter(|(_, elements)| elements.len() >= 3)
+            .collect();
+        let (&family, elements) = families.choose(rng)?;
+        let others: Vec<_> = self
+            .families
+            .iter()
+            .filter(|(&f, _)| f != family)
+            .flat_map(|(_, elements)| elements)
+            .collect();
+        let &intruder = *others.choose(rng)?;
+
+        let mut family = elements.to_vec();
+        family.shuffle(rng);
+        let mut elements = [family[0], family[1], family[2], intruder];
+        elements.shuffle(rng);
+
+        Some(Round { elements, intruder })
+    }
+
+    /// Look through every possible set for one not played yet.
+    fn first_unseen(&self, rng: &mut impl Rng) -> Option<Round> {
+        for (&family, elements) in &self.families {
+            let intruders = self
+                .families
+                .iter()
+                .filter(|(&f, _)| f != family)
+                .flat_map(|(_, elements)| elements);
+            for &intruder in intruders {
+                for a in 0..elements.len() {
+                    for b in a + 1..elements.len() {
+                        for c in b + 1..elements.len() {
+                            let mut elements = [elements[a], elements[b], elements[c], intruder];
+                            if !self.seen.contains(&sorted(elements)) {
+                                elements.shuffle(rng);
+                                return Some(Round { elements, intruder });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// `elements` sorted, to compare sets whatever their order.
+fn sorted(mut elements: [Sentence; 4]) -> [Sentence; 4] {
+    elements.sort_unstable();
+    elements
+}
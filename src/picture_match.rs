@@ -0,0 +1,266 @@
+//! Matching pictures to words.
+//!
+//! See [`PictureMatch`].
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use yew::prelude::*;
+
+use crate::audio::Audio;
+use crate::family::Family;
+use crate::score::{Mistake, Score};
+use crate::sentences::{shuffled, Sentence};
+use crate::style;
+
+/// Number of choices for each picture, the right one included.
+const CHOICES: usize = 4;
+
+/// Quiz component: the illustration of an element is shown with the logo of its
+/// family, the student listens to the choices and picks the right one.
+///
+/// Only the elements with an illustration are asked.
+pub struct PictureMatch {
+    /// Says the choices.
+    audio: Audio,
+    /// Elements left to ask about, the next one is last.
+    remaining: Vec<Sentence>,
+    /// Answers so far.
+    score: Score,
+    /// What is shown to the student.
+    stage: Stage,
+}
+
+/// What is shown to the student.
+#[derive(Debug, Clone, PartialEq)]
+enum Stage {
+    /// Waiting for a click, browsers only play sounds after one.
+    Start,
+    /// The picture is shown, waiting for the answer.
+    Question {
+        sentence: Sentence,
+        /// The right answer and the distractors, in a random order.
+        choices: Vec<Sentence>,
+    },
+    /// The student answered, showing whether it was right.
+    Answered {
+        sentence: Sentence,
+        choices: Vec<Sentence>,
+        given: Sentence,
+    },
+    /// All the pictures were asked.
+    Over,
+}
+
+#[derive(Debug)]
+pub enum PictureMatchMsg {
+    /// Go to the next picture, or start the quiz.
+    Next,
+    /// Say a choice.
+    Play(Sentence),
+    /// The student chose an element.
+    Answer(Sentence),
+    /// Ask all the pictures again, in another order.
+    Restart,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct PictureMatchProps {
+    /// Elements to ask about, those without an illustration are ignored.
+    pub selection: Vec<Sentence>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for PictureMatch {
+    type Message = PictureMatchMsg;
+    type Properties = PictureMatchProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            audio: Audio::new(|_| ()),
+            remaining: with_image(&ctx.props().selection),
+            score: Score::default(),
+            stage: Stage::Start,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match (&self.stage, msg) {
+            (Stage::Start | Stage::Answered { .. }, PictureMatchMsg::Next) => {
+                self.stage = match self.remaining.pop() {
+                    Some(sentence) => Stage::Question {
+                        sentence,
+                        choices: choices(sentence, &mut OsRng),
+                    },
+                    None => Stage::Over,
+                };
+            }
+            (Stage::Question { .. } | Stage::Answered { .. }, PictureMatchMsg::Play(choice)) => {
+                self.audio.play(&choice.element_sound());
+                return false;
+            }
+            (Stage::Question { sentence, choices }, PictureMatchMsg::Answer(given)) => {
+                let sentence = *sentence;
+                self.audio.pause();
+                if given == sentence {
+                    self.score.right(sentence);
+                } else {
+                    self.score.wrong(Mistake {
+                        sentence,
+                        expected: sentence.label().to_owned(),
+                        given: given.label().to_owned(),
+                    });
+                }
+                self.stage = Stage::Answered {
+                    sentence,
+                    choices: choices.clone(),
+                    given,
+                };
+            }
+            (Stage::Over, PictureMatchMsg::Restart) => {
+                self.remaining = with_image(&ctx.props().selection);
+                self.score = Score::default();
+                self.stage = Stage::Start;
+            }
+            (_, PictureMatchMsg::Exit) => {
+                self.audio.pause();
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let content = match &self.stage {
+            Stage::Start if self.remaining.is_empty() => html! {
+                <p> { "Aucun des éléments choisis n'a d'illustration." } </p>
+            },
+            Stage::Start => html! {
+                <>
+                    <p> { "Regardez chaque image, écoutez les propositions et choisissez le bon mot." } </p>
+                    <button onclick={ link.callback(|_| PictureMatchMsg::Next) }> { "Commencer" } </button>
+                </>
+            },
+            Stage::Question { sentence, choices } => html! {
+                <>
+                    { picture(*sentence) }
+                    { for choices.iter().enumerate().map(|(index, &choice)| html! {
+                        <p>
+                            <button onclick={ link.callback(move |_| PictureMatchMsg::Play(choice)) }>
+                                { format!("Écouter la proposition {}", index + 1) }
+                            </button>
+                            <button onclick={ link.callback(move |_| PictureMatchMsg::Answer(choice)) }>
+                                { "C'est celle-ci" }
+                            </button>
+                        </p>
+                    }) }
+                    <hr />
+                    { self.score.render() }
+                </>
+            },
+            Stage::Answered {
+                sentence,
+                choices,
+                given,
+            } => html! {
+                <>
+                    { picture(*sentence) }
+                    if given == sentence {
+                        <p> { format!("Bonne réponse : {}", sentence.label()) } </p>
+                    } else {
+                        <p> { format!("Non, c'était : {}", sentence.label()) } </p>
+                    }
+                    // The right choice is shown selected.
+                    { for choices.iter().enumerate().map(|(index, &choice)| html! {
+                        <p>
+                            <button
+                                onclick={ link.callback(move |_| PictureMatchMsg::Play(choice)) }
+                                class={ style::button_select_family(choice == *sentence) }
+                            >
+                                { format!("{}. {}", index + 1, choice.label()) }
+                            </button>
+                        </p>
+                    }) }
+                    <hr />
+                    { self.score.render() }
+                    <button onclick={ link.callback(|_| PictureMatchMsg::Next) }> { "Image suivante" } </button>
+                </>
+            },
+            Stage::Over => html! {
+                <>
+                    { self.score.render_recap() }
+                    <button onclick={ link.callback(|_| PictureMatchMsg::Restart) }> { "Recommencer" } </button>
+                </>
+            },
+        };
+
+        html! {
+            <>
+                { content }
+                <hr />
+                <button onclick={ link.callback(|_| PictureMatchMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+            </>
+        }
+    }
+}
+
+/// The elements of `selection` with an illustration, in a random order.
+fn with_image(selection: &[Sentence]) -> Vec<Sentence> {
+    let mut remaining = shuffled(selection);
+    remaining.retain(|st| st.image_file().is_some());
+    remaining
+}
+
+/// `answer` and up to [`CHOICES`] - 1 other elements, in a random order.
+///
+/// The other elements come from the family of `answer` first, to make it harder,
+/// then from the rest of the deck.
+fn choices(answer: Sentence, rng: &mut impl Rng) -> Vec<Sentence> {
+    let family = answer.family();
+    let mut same: Vec<_> = Sentence::all_in(family)
+        .filter(|&st| st != answer)
+        .collect();
+    let mut others: Vec<_> = Family::all()
+        .filter(|&f| f != family)
+        .flat_map(Sentence::all_in)
+        .collect();
+    same.shuffle(rng);
+    others.shuffle(rng);
+
+    let mut choices: Vec<_> = same
+        .into_iter()
+        .chain(others)
+        .take(CHOICES - 1)
+        .chain([answer])
+        .collect();
+    choices.shuffle(rng);
+    choices
+}
+
+/// Illustration of the element with the logo of its family.
+fn picture(sentence: Sentence) -> Html {
+    let family = sentence.family();
+
+    html! {
+        <>
+            <p>
+                <img
+                    src={ family.logo_file() }
+                    alt={ format!("Logo de la famille {family}") }
+                    style={ family.logo_style(false).to_string() }
+                />
+                { format!("Famille {family}") }
+            </p>
+            if let Some(image) = sentence.image_file() {
+                <img src={ image } alt="Élément à retrouver" class={ style::element_image() } />
+            }
+        </>
+    }
+}
@@ -0,0 +1,232 @@
+//! Spaced repetition of the elements, remembered in the browser.
+//!
+//! See [`Memory`] and [`Revision`].
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::dictation::{Dictation, DictationProps};
+use crate::family::Family;
+use crate::sentences::Sentence;
+use crate::storage;
+
+/// Key of the [`Memory`] in the local storage of the browser.
+const STORAGE_KEY: &str = "seven-families-revision";
+/// Days before an element is due again, for each Leitner box.
+const INTERVALS: [i64; 6] = [1, 2, 4, 8, 16, 32];
+/// Elements never answered added to each revision, so that they are learned
+/// little by little.
+const NEW_PER_DAY: usize = 10;
+/// Milliseconds in a day.
+const DAY_MS: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// What the student knows of each element, following the Leitner system:
+///
+/// - A right answer moves the element to the next box, a wrong one back to the first.
+/// - The higher the box, the longer before the element is due again (see [`INTERVALS`]).
+///
+/// Elements are recorded by [id][Sentence::id()] so that the memory survives
+/// changes to the deck.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Memory(HashMap<String, Card>);
+
+/// What the student knows of an element.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Card {
+    /// Leitner box, an index in [`INTERVALS`].
+    level: usize,
+    /// Day the element should be revised, see [`today()`].
+    due: i64,
+}
+
+impl Memory {
+    /// The memory saved in the browser, empty if there is none (or if it cannot
+    /// be read).
+    pub fn load() -> Self {
+        storage::load(STORAGE_KEY).unwrap_or_default()
+    }
+
+    /// Save the memory in the browser, silently ignored if storage is not available.
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    /// Record an answer about `sentence` given on `day`.
+    pub fn answer(&mut self, sentence: Sentence, right: bool, day: i64) {
+        let card = self
+            .0
+            .entry(sentence.id())
+            .or_insert(Card { level: 0, due: day });
+        card.level = if right {
+            (card.level + 1).min(INTERVALS.len() - 1)
+        } else {
+            0
+        };
+        card.due = day + INTERVALS[card.level];
+    }
+
+    /// Elements to revise on `day`, across all families: those already answered
+    /// that are due, the most overdue first, then up to [`NEW_PER_DAY`] new ones.
+    pub fn due(&self, day: i64) -> Vec<Sentence> {
+        let all = Family::all().flat_map(Sentence::all_in);
+        let (mut due, mut new) = (Vec::new(), Vec::new());
+        for sentence in all {
+            match self.0.get(&sentence.id()) {
+                Some(card) if card.due <= day => due.push((card.due, sentence)),
+                Some(_) => (),
+                None => new.push(sentence),
+            }
+        }
+        due.sort_unstable();
+
+        due.into_iter()
+            .map(|(_, sentence)| sentence)
+            .chain(new.into_iter().take(NEW_PER_DAY))
+            .collect()
+    }
+}
+
+/// Record an answer given today to a quiz, in the memory saved in the browser.
+pub fn record(sentence: Sentence, right: bool) {
+    let mut memory = Memory::load();
+    memory.answer(sentence, right, today());
+    memory.save();
+}
+
+/// Number of the current day, in the local time of the student.
+pub fn today() -> i64 {
+    let now = js_sys::Date::new_0();
+    let offset_ms = now.get_timezone_offset() * 60.0 * 1000.0;
+    ((now.get_time() - offset_ms) / DAY_MS).floor() as i64
+}
+
+/// Revision component: a [dictation][Dictation] of the elements due today,
+/// whatever the selected families.
+pub struct Revision {
+    /// Elements due when the revision started.
+    due: Vec<Sentence>,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct RevisionProps {
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for Revision {
+    type Message = ();
+    type Properties = RevisionProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            due: Memory::load().due(today()),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_exit = ctx.props().on_exit.clone();
+
+        if self.due.is_empty() {
+            return html! {
+                <>
+                    <p> { "Rien à réviser aujourd'hui, revenez demain !" } </p>
+                    <button onclick={ on_exit.reform(|_| ()) }> { "Retourner à la sélection de familles" } </button>
+                </>
+            };
+        }
+
+        let props = DictationProps {
+            selection: self.due.clone(),
+            on_exit,
+        };
+        html! {
+            <>
+                <p> { format!("{} élément(s) à réviser aujourd'hui", self.due.len()) } </p>
+                <Dictation ..props />
+            </>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::test_deck::card;
+
+    /// Every element of the deck, in order.
+    fn all() -> Vec<Sentence> {
+        Family::all().flat_map(Sentence::all_in).collect()
+    }
+
+    #[test]
+    fn answers_move_between_boxes() {
+        let sentence = card(0, 0);
+        let mut memory = Memory::default();
+
+        // Each right answer moves to the next box, until the last one.
+        let mut day = 0;
+        for (level, interval) in INTERVALS.into_iter().enumerate().skip(1) {
+            memory.answer(sentence, true, day);
+            let card = memory.0[&sentence.id()];
+            assert_eq!(card.level, level);
+            assert_eq!(card.due, day + interval);
+            day = card.due;
+        }
+        memory.answer(sentence, true, day);
+        let card = memory.0[&sentence.id()];
+        assert_eq!(card.level, INTERVALS.len() - 1);
+        assert_eq!(card.due, day + 32);
+
+        // A wrong answer goes back to the first box.
+        memory.answer(sentence, false, day);
+        let card = memory.0[&sentence.id()];
+        assert_eq!(card.level, 0);
+        assert_eq!(card.due, day + 1);
+    }
+
+    #[test]
+    fn due_today() {
+        let mut memory = Memory::default();
+        // Due in 2 days.
+        memory.answer(card(0, 0), true, 0);
+        // Due tomorrow.
+        memory.answer(card(0, 1), false, 0);
+        // Overdue for 5 days.
+        memory.answer(card(0, 2), false, -5);
+
+        let new: Vec<_> = all()
+            .into_iter()
+            .filter(|&st| ![card(0, 0), card(0, 1), card(0, 2)].contains(&st))
+            .take(NEW_PER_DAY)
+            .collect();
+        // Nothing answered is due yet, only new elements.
+        assert_eq!(memory.due(-5), new);
+        assert_eq!(memory.due(0)[0], card(0, 2));
+        assert_eq!(memory.due(0)[1..], new);
+
+        // The most overdue first, then the others due, then the new ones.
+        let due = memory.due(1);
+        assert_eq!(due[..2], [card(0, 2), card(0, 1)]);
+        assert_eq!(due[2..], new);
+
+        let due = memory.due(2);
+        assert_eq!(due[..3], [card(0, 2), card(0, 1), card(0, 0)]);
+        assert_eq!(due.len(), 3 + NEW_PER_DAY);
+    }
+
+    #[test]
+    fn new_elements_per_day() {
+        let all = all();
+
+        // A fresh memory only gives the first elements of the deck.
+        assert_eq!(Memory::default().due(0), all[..NEW_PER_DAY]);
+
+        // When fewer are left, only them.
+        let mut memory = Memory::default();
+        for &sentence in &all[..all.len() - 4] {
+            memory.answer(sentence, true, 0);
+        }
+        assert_eq!(memory.due(0), all[all.len() - 4..]);
+    }
+}
@@ -0,0 +1,71 @@
+//! Game in progress, saved in the browser to survive a reload of the page.
+//!
+//! See [`SavedGame`].
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bingo::Grid;
+use crate::game::SentenceState;
+use crate::history::History;
+use crate::sentences::{DrawStrategy, Sentence, Sentences};
+use crate::storage;
+
+/// Key of the [`SavedGame`] in the local storage of the browser.
+const STORAGE_KEY: &str = "seven-families-game";
+
+/// Everything needed to resume a game where it was, except the timers: the game
+/// is always resumed paused.
+///
+/// Elements are saved by [id][Sentence::id()], a save that does not match the
+/// deck anymore is ignored.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// Time interval between each sentence.
+    pub duration: Duration,
+    /// How the sentences are drawn.
+    pub strategy: DrawStrategy,
+    /// Whether the pronunciation of the elements is shown.
+    pub phonetics: bool,
+    /// Sentences of the game, drawn or not.
+    pub sentences: Sentences,
+    /// Sentences already drawn.
+    pub history: History,
+    /// Grids of the bingo the game is the draw of, empty if it is not one.
+    pub bingo: Vec<Grid>,
+    /// Where the game was.
+    pub state: SavedState,
+}
+
+/// Where a saved game was, paused or not.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SavedState {
+    /// Not started yet, choosing the settings.
+    Settings,
+    /// Saying a sentence.
+    Playing {
+        current: (Sentence, SentenceState),
+        countdown: Option<Duration>,
+    },
+    /// Waiting for the next sentence.
+    Waiting { last: Sentence, time_left: Duration },
+}
+
+impl SavedGame {
+    /// The game saved in the browser, if there is one that can be read.
+    ///
+    /// The deck must be [installed][crate::deck::Deck::install()] first.
+    pub fn load() -> Option<Self> {
+        storage::load(STORAGE_KEY)
+    }
+
+    /// Save the game in the browser, replacing the previous one.
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    /// Forget the saved game, once it is finished or abandoned.
+    pub fn clear() {
+        storage::remove(STORAGE_KEY);
+    }
+}
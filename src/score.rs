@@ -0,0 +1,78 @@
+//! Score of the quiz modes.
+//!
+//! See [`Score`].
+use yew::prelude::*;
+
+use crate::revision;
+use crate::sentences::Sentence;
+
+/// Answers of a student during a quiz, kept to show them their mistakes at the end.
+///
+/// Each answer is also [recorded][revision::record()] for the spaced repetition.
+#[derive(Debug, Default)]
+pub struct Score {
+    /// Number of questions answered.
+    answered: usize,
+    /// Wrong answers, in order.
+    mistakes: Vec<Mistake>,
+}
+
+/// A wrong answer.
+#[derive(Debug, Clone)]
+pub struct Mistake {
+    /// Element the question was about.
+    pub sentence: Sentence,
+    /// Right answer, as shown to the student.
+    pub expected: String,
+    /// Answer of the student, as shown to them.
+    pub given: String,
+}
+
+impl Score {
+    /// Record a right answer about `sentence`.
+    pub fn right(&mut self, sentence: Sentence) {
+        self.answered += 1;
+        revision::record(sentence, true);
+    }
+
+    /// Record a wrong answer.
+    pub fn wrong(&mut self, mistake: Mistake) {
+        self.answered += 1;
+        revision::record(mistake.sentence, false);
+        self.mistakes.push(mistake);
+    }
+
+    /// Score so far, shown during the quiz.
+    pub fn render(&self) -> Html {
+        html! {
+            <p> { format!("Score : {} / {}", self.answered - self.mistakes.len(), self.answered) } </p>
+        }
+    }
+
+    /// Final score with the list of mistakes.
+    pub fn render_recap(&self) -> Html {
+        html! {
+            <div>
+                <h2> { "Quiz terminé !" } </h2>
+                { self.render() }
+                if self.mistakes.is_empty() {
+                    <p> { "Aucune erreur, bravo !" } </p>
+                } else {
+                    <h3> { format!("Erreurs ({})", self.mistakes.len()) } </h3>
+                    <ul>
+                        { for self.mistakes.iter().map(|mistake| html! {
+                            <li>
+                                { format!(
+                                    "{} : « {} » au lieu de « {} »",
+                                    mistake.sentence.label(),
+                                    mistake.given,
+                                    mistake.expected
+                                ) }
+                            </li>
+                        }) }
+                    </ul>
+                }
+            </div>
+        }
+    }
+}
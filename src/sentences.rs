@@ -1,278 +1,630 @@
 //! Sentences for the game.
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use enum_iterator::IntoEnumIterator;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use yew::html::Scope;
+use yew::prelude::*;
 
+use crate::audio::Sound;
+use crate::deck::ElementData;
 use crate::family::Family;
+use crate::game::{BeforeGameMsg, Game};
+use crate::manifest::Gender;
+use crate::style;
 
 /// Sentences for a game.
 ///
-/// Shuffled once on creation and never again.
-#[derive(Debug)]
-pub struct Sentences(Vec<Sentence>);
+/// Ordered once on creation (see [`DrawStrategy`]) and never again, with a seeded RNG:
+/// the same seed, strategy and selection always give the same order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sentences {
+    /// Selected sentences, sorted so that the order only depends on the seed.
+    pool: Vec<Sentence>,
+    /// Sentences not yet drawn, the next one is last.
+    remaining: Vec<Sentence>,
+    /// Number of sentences in the game, drawn or not.
+    total: usize,
+    /// Seed used to order the sentences.
+    seed: u64,
+    /// How the sentences are ordered.
+    strategy: DrawStrategy,
+}
 
 impl Sentences {
-    /// Build a new set of sentences from the selected families.
+    /// Build a new set of sentences from the selected elements.
     ///
-    /// Initially, all the possible sentences are available in random order
+    /// Initially, all the sentences are available in the order given by `strategy`
     /// and they are popped by [`Self::draw_one()`].
-    pub fn new(families: &HashSet<Family>) -> Self {
-        // There are 6 elements per family
-        let mut sentences = Vec::with_capacity(families.len() * 6);
-        for family in families {
-            match family {
-                Family::ChiefKit => {
-                    sentences.extend(ChiefKit::into_enum_iter().map(Sentence::ChiefKit))
-                }
-                Family::Fruits => sentences.extend(Fruits::into_enum_iter().map(Sentence::Fruits)),
-                Family::Hygiene => {
-                    sentences.extend(Hygiene::into_enum_iter().map(Sentence::Hygiene))
-                }
-                Family::ProfessionalGestures => sentences.extend(
-                    ProfessionalGestures::into_enum_iter().map(Sentence::ProfessionalGestures),
-                ),
-                Family::RedFruits => {
-                    sentences.extend(RedFruits::into_enum_iter().map(Sentence::RedFruits))
-                }
-                Family::SmallUstensils => {
-                    sentences.extend(SmallUstensils::into_enum_iter().map(Sentence::SmallUstensils))
-                }
-                Family::Trimmings => {
-                    sentences.extend(Trimmings::into_enum_iter().map(Sentence::Trimmings))
-                }
-            }
-        }
+    pub fn new(selection: &HashSet<Sentence>, seed: u64, strategy: DrawStrategy) -> Self {
+        let mut pool: Vec<_> = selection.iter().copied().collect();
+        // The iteration order of a `HashSet` is random, sort first so that only
+        // the seed decides the final order.
+        pool.sort_unstable();
+
+        let mut sentences = Self {
+            pool,
+            remaining: Vec::new(),
+            total: 0,
+            seed,
+            strategy,
+        };
+        sentences.reorder();
+        sentences
+    }
+
+    /// A random seed, short enough to be shared by hand.
+    pub fn random_seed() -> u64 {
+        rand::rngs::OsRng.gen_range(0..1_000_000)
+    }
+
+    /// Order all the sentences again, using `seed`.
+    ///
+    /// Meant to be used before the first [draw][Self::draw_one()].
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.reorder();
+    }
+
+    /// Order all the sentences again, using `strategy`.
+    ///
+    /// Meant to be used before the first [draw][Self::draw_one()].
+    pub fn set_strategy(&mut self, strategy: DrawStrategy) {
+        self.strategy = strategy;
+        self.reorder();
+    }
 
-        sentences.shuffle(&mut rand::rngs::OsRng);
-        Self(sentences)
+    /// Seed used to order the sentences.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of selected elements, which can differ from [`Self::total()`]
+    /// with [`DrawStrategy::WithReplacement`].
+    pub fn selected(&self) -> usize {
+        self.pool.len()
     }
 
     /// Draw one sentence from the list.
     pub fn draw_one(&mut self) -> Option<Sentence> {
-        self.0.pop()
+        self.remaining.pop()
+    }
+
+    /// Put a drawn sentence back, it will be the next one drawn.
+    pub fn put_back(&mut self, sentence: Sentence) {
+        self.remaining.push(sentence);
     }
 
     /// `true` if there are no more sentences.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.remaining.is_empty()
+    }
+
+    /// Number of sentences already drawn.
+    pub fn drawn(&self) -> usize {
+        self.total - self.remaining.len()
+    }
+
+    /// Number of sentences in the game, drawn or not.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// `true` if all the selected elements of `family` have been drawn.
+    pub fn is_family_done(&self, family: Family) -> bool {
+        !self.remaining.iter().any(|st| st.family() == family)
+    }
+
+    /// Fill [`Self::remaining`] from the pool, with the current seed and strategy.
+    fn reorder(&mut self) {
+        let rng = &mut ChaCha8Rng::seed_from_u64(self.seed);
+        let pool = self.pool.clone();
+
+        let mut order = match self.strategy {
+            DrawStrategy::Shuffle => {
+                let mut pool = pool;
+                pool.shuffle(rng);
+                pool
+            }
+            DrawStrategy::NoConsecutiveFamily => no_consecutive_family(pool, rng),
+            DrawStrategy::FamilyByFamily => shuffled_families(&pool, rng).concat(),
+            DrawStrategy::RoundRobin => {
+                let families = shuffled_families(&pool, rng);
+                let rounds = families.iter().map(Vec::len).max().unwrap_or(0);
+                (0..rounds)
+                    .flat_map(|round| families.iter().filter_map(move |f| f.get(round)))
+                    .copied()
+                    .collect()
+            }
+            DrawStrategy::WithReplacement { rounds } => (0..rounds)
+                .filter_map(|_| pool.choose(rng).copied())
+                .collect(),
+        };
+
+        // Sentences are popped from the end.
+        order.reverse();
+        self.total = order.len();
+        self.remaining = order;
     }
 }
 
-/// All the possible sentences.
-#[derive(Debug, Clone, Copy)]
-pub enum Sentence {
-    /// Ustensils used by a chef when cooking.
-    ChiefKit(ChiefKit),
-    /// Other fruits, like oranges.
-    Fruits(Fruits),
-    /// Hygiene is important for a cook.
-    Hygiene(Hygiene),
-    /// Flipping pancakes is a professional gesture.
-    ProfessionalGestures(ProfessionalGestures),
-    /// Red fruits, like strawberries.
-    RedFruits(RedFruits),
-    /// Small tools used in cooking that are not part
-    /// of a chief's kit.
-    SmallUstensils(SmallUstensils),
-    /// Trimmings, like cutting cucumbers.
-    Trimmings(Trimmings),
+/// How the sentences of a game are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DrawStrategy {
+    /// All the sentences, uniformly shuffled.
+    Shuffle,
+    /// All the sentences, shuffled, but never two of the same family in a row
+    /// (unless there is no other way).
+    NoConsecutiveFamily,
+    /// One family after the other, in deck order, shuffled inside each family.
+    FamilyByFamily,
+    /// One sentence of each family in turn, in deck order, shuffled inside
+    /// each family.
+    RoundRobin,
+    /// `rounds` sentences drawn with replacement: the same sentence can be
+    /// said several times and some may never be.
+    WithReplacement { rounds: usize },
 }
 
-impl Sentence {
-    /// Sound file for the whole family.
-    pub fn family_sound_file(&self) -> &'static str {
-        match self {
-            Sentence::ChiefKit(st) => st.family_sound_file(),
-            Sentence::Fruits(st) => st.family_sound_file(),
-            Sentence::Hygiene(st) => st.family_sound_file(),
-            Sentence::ProfessionalGestures(st) => st.family_sound_file(),
-            Sentence::RedFruits(st) => st.family_sound_file(),
-            Sentence::SmallUstensils(st) => st.family_sound_file(),
-            Sentence::Trimmings(st) => st.family_sound_file(),
-        }
+impl DrawStrategy {
+    /// All the strategies, as shown to players.
+    ///
+    /// [`DrawStrategy::WithReplacement`] is given with `rounds` draws.
+    pub fn all(rounds: usize) -> [Self; 5] {
+        [
+            Self::Shuffle,
+            Self::NoConsecutiveFamily,
+            Self::FamilyByFamily,
+            Self::RoundRobin,
+            Self::WithReplacement { rounds },
+        ]
     }
 
-    /// Sound file for the specific element.
-    pub fn element_sound_file(&self) -> &'static str {
+    /// Description shown to players.
+    pub fn label(&self) -> &'static str {
         match self {
-            Sentence::ChiefKit(st) => st.element_sound_file(),
-            Sentence::Fruits(st) => st.element_sound_file(),
-            Sentence::Hygiene(st) => st.element_sound_file(),
-            Sentence::ProfessionalGestures(st) => st.element_sound_file(),
-            Sentence::RedFruits(st) => st.element_sound_file(),
-            Sentence::SmallUstensils(st) => st.element_sound_file(),
-            Sentence::Trimmings(st) => st.element_sound_file(),
+            Self::Shuffle => "Ordre aléatoire",
+            Self::NoConsecutiveFamily => "Jamais deux fois la même famille de suite",
+            Self::FamilyByFamily => "Famille par famille",
+            Self::RoundRobin => "Chaque famille à tour de rôle",
+            Self::WithReplacement { .. } => "Tirage avec remise",
         }
     }
 }
 
-/// Generate a `sound_file` method on `$name`.
+/// Sentences grouped by family, in deck order, shuffled inside each family.
+///
+/// `pool` must be sorted.
+fn shuffled_families(pool: &[Sentence], rng: &mut ChaCha8Rng) -> Vec<Vec<Sentence>> {
+    pool.chunk_by(|a, b| a.family == b.family)
+        .map(|family| {
+            let mut family = family.to_vec();
+            family.shuffle(rng);
+            family
+        })
+        .collect()
+}
+
+/// Shuffle `pool` so that two sentences in a row are never of the same family.
 ///
-/// The file for `$name::$variant` is `assets / $folder / $file .mp3`.
-macro_rules! assets {
-    ($(#[$meta:meta])* $name:ident: $folder:literal; $($(#[$variant_meta:meta])* $variant:ident: $file:literal),+ $(,)?) => {
-        $(#[$meta])*
-        #[derive(Debug, Clone, Copy, IntoEnumIterator)]
-        pub enum $name {
-            $(
-                $(#[$variant_meta])*
-                $variant,
-            )+
+/// When this is impossible (for example with a single family), sentences of the
+/// most common family are spread as much as possible.
+fn no_consecutive_family(mut pool: Vec<Sentence>, rng: &mut ChaCha8Rng) -> Vec<Sentence> {
+    let mut order = Vec::with_capacity(pool.len());
+    let mut last = None;
+
+    while !pool.is_empty() {
+        let mut counts = HashMap::<Family, usize>::new();
+        for st in &pool {
+            *counts.entry(st.family).or_default() += 1;
         }
 
-        impl $name {
-            /// Path to the sound file for the family, absolute from the root
-            /// of the website.
-            const fn family_sound_file(&self) -> &'static str {
-                // Ensure the file exists.
-                const _: &[u8] = include_bytes!(concat!("../assets/", $folder, "/0-famille.mp3")).as_slice();
-                // Relative to the root of the website.
-                // Adapted to github pages.
-                if crate::IS_FOR_GH_PAGES {
-                    concat!("/seven-families-wasm/assets/", $folder, "/0-famille.mp3")
+        // After drawing one sentence of `family`, can the `rest` still be ordered ?
+        // Only if no family fills more than half of it, with `family` unable to
+        // come first.
+        let rest = pool.len() - 1;
+        let feasible = |family: Family| {
+            counts.iter().all(|(&f, &count)| {
+                if f == family {
+                    count - 1 <= rest / 2
                 } else {
-                    concat!("/assets/", $folder, "/0-famille.mp3")
+                    count <= rest.div_ceil(2)
                 }
-            }
+            })
+        };
 
-            /// Path to the sound file for the sentence, absolute from the root of
-            /// the website.
-            const fn element_sound_file(&self) -> &'static str {
-                // Ensure the file exists.
-                $( const _: &[u8] = include_bytes!(concat!("../assets/", $folder, "/", $file, ".mp3")).as_slice(); )+
-                match self {
-                    // Relative to the root of the website.
-                    // Adapted to github pages.
-                    $(
-                        Self::$variant => if crate::IS_FOR_GH_PAGES {
-                            concat!("/seven-families-wasm/assets/", $folder, "/", $file, ".mp3")
-                        } else {
-                            concat!("/assets/", $folder, "/", $file, ".mp3")
-                        },
-                    )+
-                }
-            }
+        let mut candidates: Vec<_> = (0..pool.len())
+            .filter(|&i| Some(pool[i].family) != last && feasible(pool[i].family))
+            .collect();
+        if candidates.is_empty() {
+            // Already impossible: keep spreading the most common family among the others.
+            let most_common = counts
+                .iter()
+                .filter(|(&f, _)| Some(f) != last)
+                .max_by_key(|(&f, &count)| (count, f))
+                .map(|(&f, _)| f);
+            candidates = (0..pool.len())
+                .filter(|&i| Some(pool[i].family) == most_common.or(last))
+                .collect();
         }
-    };
-}
 
-assets! {
-    /// Malette
-    ChiefKit: "mallette";
-    /// Canneleur
-    Coring: "canneleur",
-    /// Filet de sole
-    FilletKnife: "filet-de-sole",
-    /// Couteau d'office
-    ParingKnife: "couteau-d-office",
-    /// Économe
-    Peeler: "econome",
-    /// Éminceur
-    Slicer: "eminceur",
-    /// Zesteur,
-    Zester: "zesteur",
+        let i = *candidates
+            .choose(rng)
+            .expect("the pool is not empty so there is always a candidate");
+        let st = pool.remove(i);
+        last = Some(st.family);
+        order.push(st);
+    }
+
+    order
 }
 
-assets! {
-    /// Fruits
-    Fruits: "fruits";
-    /// Pomme
-    Apple: "pomme",
-    /// Abricot
-    Apricot: "abricot",
-    /// Raisin
-    Grapes: "raisin",
-    /// Orange
-    Orange: "orange",
-    /// Pêche
-    Peach: "peche",
-    /// Prune
-    Plum: "prune",
+/// `selection` in a random order, for the modes that do not need to be replayed
+/// with a seed.
+pub fn shuffled(selection: &[Sentence]) -> Vec<Sentence> {
+    let mut shuffled = selection.to_vec();
+    shuffled.shuffle(&mut rand::rngs::OsRng);
+    shuffled
 }
 
-assets! {
-    /// Hygiène
-    Hygiene: "hygiene";
-    /// Bactérie
-    Bacterium: "bacterie",
-    /// Nettoyage
-    Cleaning: "nettoyage",
-    /// Désinfectant
-    Disinfectant: "desinfectant",
-    /// EPI
-    Epi: "epi",
-    /// Microbe
-    Microbe: "microbe",
-    /// Moisissure
-    Mould: "moisissure",
+/// One of the possible sentences: an element of a family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sentence {
+    /// Family of the element.
+    family: Family,
+    /// Index of the element in the family.
+    element: usize,
 }
 
-assets! {
-    /// Gestes professionnels
-    ProfessionalGestures: "gestes-professionnels";
-    /// Escalopper
-    Cutletting: "escalopper",
-    /// Abaisser
-    Lower: "abaisser",
-    /// Émincer
-    Slice: "emincer",
-    /// Suer
-    Sweat: "suer",
-    /// Tourner
-    Turn: "tourner",
-    /// Vanner
-    Winnow: "vanner",
+impl Sentence {
+    /// All the sentences of a family, in deck order.
+    pub fn all_in(family: Family) -> impl Iterator<Item = Self> {
+        (0..family.data().elements.len()).map(move |element| Self { family, element })
+    }
+
+    /// The sentence with the given [id][Self::id()], if it is in the deck.
+    pub fn from_id(id: &str) -> Option<Self> {
+        Family::all()
+            .flat_map(Self::all_in)
+            .find(|sentence| sentence.id() == id)
+    }
+
+    /// Family the sentence belongs to.
+    pub fn family(&self) -> Family {
+        self.family
+    }
+
+    /// Name of the element, as shown to players.
+    pub fn label(&self) -> &'static str {
+        &self.data().label
+    }
+
+    /// Identifier of the element, `family/element` from the manifest ids.
+    ///
+    /// Unlike the element itself, it stays the same when the deck changes.
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.family.data().id, self.data().id)
+    }
+
+    /// Sound file for the whole family, if it was recorded.
+    pub fn family_sound_file(&self) -> Option<&'static str> {
+        self.family.data().sound_file.as_deref()
+    }
+
+    /// Sound file for the specific element, if it was recorded.
+    pub fn element_sound_file(&self) -> Option<&'static str> {
+        self.data().sound_file.as_deref()
+    }
+
+    /// First half of the sentence: "Dans la famille ...".
+    ///
+    /// Falls back to speech synthesis when the family has no recording.
+    pub fn family_sound(&self) -> Sound {
+        match self.family_sound_file() {
+            Some(file) => Sound::File(file),
+            None => Sound::Speech(format!("Dans la famille {}", self.family)),
+        }
+    }
+
+    /// Second half of the sentence: "je voudrais ...".
+    ///
+    /// Falls back to speech synthesis when the element has no recording.
+    pub fn element_sound(&self) -> Sound {
+        match self.element_sound_file() {
+            Some(file) => Sound::File(file),
+            None => Sound::Speech(format!("je voudrais {}", self.label())),
+        }
+    }
+
+    /// Illustration for the specific element, if there is one.
+    pub fn image_file(&self) -> Option<&'static str> {
+        self.data().image_file.as_deref()
+    }
+
+    /// Grammatical gender of the element, if it is a noun.
+    pub fn gender(&self) -> Option<Gender> {
+        self.data().gender
+    }
+
+    /// Name of the element with its article: "la brunoise", "l'écumoire".
+    ///
+    /// Acronyms like "EPI" keep their case, elements without an article (verbs)
+    /// are only their label.
+    pub fn with_article(&self) -> String {
+        let label = self.label();
+        let article = match self.data().article.as_deref() {
+            Some(article) => article,
+            None => return label.to_string(),
+        };
+        let label = if label.chars().all(|c| !c.is_lowercase()) {
+            label.to_string()
+        } else {
+            let mut chars = label.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        };
+
+        if article.ends_with('\'') {
+            format!("{article}{label}")
+        } else {
+            format!("{article} {label}")
+        }
+    }
+
+    /// Short definition of the element, if there is one.
+    pub fn definition(&self) -> Option<&'static str> {
+        self.data().definition.as_deref()
+    }
+
+    /// Example of the element used in a sentence, if there is one.
+    pub fn example(&self) -> Option<&'static str> {
+        self.data().example.as_deref()
+    }
+
+    /// Pronunciation of the element in the International Phonetic Alphabet,
+    /// without slashes, if there is one.
+    pub fn ipa(&self) -> Option<&'static str> {
+        self.data().ipa.as_deref()
+    }
+
+    /// Label split in syllables, "chi·nois é·ta·mine", if there is one.
+    pub fn syllables(&self) -> Option<&'static str> {
+        self.data().syllables.as_deref()
+    }
+
+    /// Render the pronunciation of the element: syllables and IPA, empty when
+    /// neither is known.
+    pub fn render_pronunciation(&self) -> Html {
+        let parts: Vec<_> = self
+            .syllables()
+            .map(str::to_string)
+            .into_iter()
+            .chain(self.ipa().map(|ipa| format!("/{ipa}/")))
+            .collect();
+        if parts.is_empty() {
+            return Html::default();
+        }
+
+        html! {
+            <p> { parts.join(" ") } </p>
+        }
+    }
+
+    /// Render what is known about the word: article, gender, definition and example.
+    ///
+    /// Without an article nor a gender (verbs), the label alone is not repeated.
+    pub fn render_details(&self) -> Html {
+        let nature = match (self.gender(), self.data().article.is_some()) {
+            (Some(gender), _) => Some(format!("{} ({})", self.with_article(), gender.label())),
+            (None, true) => Some(self.with_article()),
+            (None, false) => None,
+        };
+
+        html! {
+            <div>
+                if let Some(nature) = nature {
+                    <p> { nature } </p>
+                }
+                if let Some(definition) = self.definition() {
+                    <p> { definition } </p>
+                }
+                if let Some(example) = self.example() {
+                    <p><em> { format!("« {example} »") } </em></p>
+                }
+            </div>
+        }
+    }
+
+    /// Both halves of the sentence, in order.
+    pub fn sounds(&self) -> [Sound; 2] {
+        [self.family_sound(), self.element_sound()]
+    }
+
+    /// Render the element's button, adapting to whether it is selected or not.
+    pub fn render(&self, link: &Scope<Game>, selected: bool) -> Html {
+        let st = *self;
+        let onclick = link.callback(move |_| BeforeGameMsg::ToggleElement(st));
+
+        html! {
+            <button {onclick} class={style::button_select_family(selected)} style={self.family.button_style(selected)}>
+                { self.label() }
+            </button>
+        }
+    }
+
+    /// Render the element as a card, with the logo of its family.
+    ///
+    /// Without `onclick`, the card cannot be clicked.
+    pub fn render_card(&self, onclick: Option<Callback<MouseEvent>>) -> Html {
+        let disabled = onclick.is_none();
+
+        html! {
+            <button {onclick} {disabled} class={style::button_select_family(false)} style={self.family.button_style(false)}>
+                <img
+                    src={ self.family.logo_file() }
+                    alt={ format!("Logo de la famille {}", self.family) }
+                    style={ self.family.logo_style(false).to_string() }
+                />
+                { self.label() }
+            </button>
+        }
+    }
+
+    /// Data for the element in the deck.
+    fn data(&self) -> &'static ElementData {
+        &self.family.data().elements[self.element]
+    }
 }
 
-assets! {
-    /// Fruits rouges
-    RedFruits: "fruits-rouges";
-    /// Mûre
-    Blackberry: "mure",
-    /// Cassis
-    Blackcurrant: "cassis",
-    /// Cerise
-    Cherry: "cerise",
-    /// Framboise
-    Raspberry: "framboise",
-    /// Groseille
-    Redcurrant: "groseille",
-    /// Fraise
-    Strawberry: "fraise",
+/// Saved by [id][Sentence::id()], so that saves survive changes to the deck.
+impl Serialize for Sentence {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.id())
+    }
 }
 
-assets! {
-    /// Petit matériel
-    SmallUstensils: "petit-materiel";
-    /// Bahut
-    Chests: "bahut",
-    /// Cul de poule
-    ChickenButt: "cul-de-poule",
-    /// Chinois étamine
-    ChineseCheesecloth: "chinois-etamine",
-    /// Plaque à débarasser
-    CleaningPlate: "plaque-a-debarasser",
-    /// Rondeau
-    Roundel: "rondeau",
-    /// Écumoire
-    Skimmer: "ecumoire",
+/// Loading fails if the element is not in the deck anymore.
+impl<'de> Deserialize<'de> for Sentence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Self::from_id(&id).ok_or_else(|| D::Error::custom(format!("unknown element '{id}'")))
+    }
 }
 
-// Funny how many words are directly taken from French here#[derive(Debug, Clone, Copy, IntoEnumIterator)]
-assets! {
-    /// Taillages
-    Trimmings: "taillages";
-    /// Brunoise
-    Brunoise: "brunoise",
-    /// Jardinière
-    Jardiniere: "jardiniere",
-    /// Julienne
-    JulienneStrip: "julienne",
-    /// Macédoine
-    Macedonia: "macedoine",
-    /// Mirepoix
-    Mirepoix: "mirepoix",
-    /// Paysanne
-    PaysanneCut: "paysanne",
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::test_deck::{card, family};
+
+    /// Families 0 and 1 (6 elements each), 2 elements of family 2 and family 6
+    /// (3 elements).
+    fn selection() -> HashSet<Sentence> {
+        [0, 1, 6]
+            .into_iter()
+            .flat_map(|f| Sentence::all_in(family(f)))
+            .chain([card(2, 0), card(2, 3)])
+            .collect()
+    }
+
+    /// Every sentence of a game, in the order they are drawn.
+    fn draw_all(selection: &HashSet<Sentence>, seed: u64, strategy: DrawStrategy) -> Vec<Sentence> {
+        let mut sentences = Sentences::new(selection, seed, strategy);
+        std::iter::from_fn(|| sentences.draw_one()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_order() {
+        let selection = selection();
+
+        for strategy in DrawStrategy::all(30) {
+            let order = draw_all(&selection, 42, strategy);
+            assert_eq!(order, draw_all(&selection, 42, strategy), "{strategy:?}");
+            // Another set with the same elements iterates in another order.
+            assert_eq!(
+                order,
+                draw_all(&self::selection(), 42, strategy),
+                "{strategy:?}"
+            );
+            assert_ne!(order, draw_all(&selection, 43, strategy), "{strategy:?}");
+        }
+
+        // Reseeding before the first draw is the same as starting with the seed.
+        let mut sentences = Sentences::new(&selection, 1, DrawStrategy::Shuffle);
+        sentences.reseed(42);
+        let reseeded: Vec<_> = std::iter::from_fn(|| sentences.draw_one()).collect();
+        assert_eq!(reseeded, draw_all(&selection, 42, DrawStrategy::Shuffle));
+    }
+
+    #[test]
+    fn shuffle_draws_everything_once() {
+        let selection = selection();
+        let mut order = draw_all(&selection, 7, DrawStrategy::Shuffle);
+        order.sort_unstable();
+        let mut expected: Vec<_> = selection.into_iter().collect();
+        expected.sort_unstable();
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn no_consecutive_family() {
+        let selection = selection();
+
+        for seed in 0..50 {
+            let order = draw_all(&selection, seed, DrawStrategy::NoConsecutiveFamily);
+            assert_eq!(order.len(), selection.len());
+            assert!(
+                order.windows(2).all(|w| w[0].family() != w[1].family()),
+                "seed {seed}: {order:?}"
+            );
+        }
+
+        // Impossible with 6 elements of a family and 2 of another: those of the
+        // first are spread as much as possible, separated by the other two.
+        let unbalanced: HashSet<_> = Sentence::all_in(family(0))
+            .chain([card(1, 0), card(1, 1)])
+            .collect();
+        for seed in 0..50 {
+            let order = draw_all(&unbalanced, seed, DrawStrategy::NoConsecutiveFamily);
+            let families: Vec<_> = order.iter().map(|st| st.family()).collect();
+            let runs = families.chunk_by(|a, b| a == b).count();
+            assert_eq!(runs, 5, "seed {seed}: {order:?}");
+        }
+    }
+
+    #[test]
+    fn round_robin() {
+        let order = draw_all(&selection(), 3, DrawStrategy::RoundRobin);
+        let families: Vec<_> = order.iter().map(|st| st.family()).collect();
+        let [f0, f1, f2, f6] = [0, 1, 2, 6].map(family);
+
+        // Families take turns in deck order, skipping those that ran out.
+        let mut expected = Vec::new();
+        expected.extend([f0, f1, f2, f6].repeat(2));
+        expected.extend([f0, f1, f6]);
+        expected.extend([f0, f1].repeat(3));
+        assert_eq!(families, expected);
+    }
+
+    #[test]
+    fn family_by_family() {
+        for seed in 0..10 {
+            let order = draw_all(&selection(), seed, DrawStrategy::FamilyByFamily);
+            let families: Vec<_> = order.iter().map(|st| st.family()).collect();
+            let runs: Vec<_> = families
+                .chunk_by(|a, b| a == b)
+                .map(|run| (run[0], run.len()))
+                .collect();
+            assert_eq!(
+                runs,
+                [
+                    (family(0), 6),
+                    (family(1), 6),
+                    (family(2), 2),
+                    (family(6), 3)
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn with_replacement() {
+        let selection = selection();
+        let rounds = 3 * selection.len();
+
+        let sentences = Sentences::new(&selection, 5, DrawStrategy::WithReplacement { rounds });
+        assert_eq!(sentences.total(), rounds);
+        assert_eq!(sentences.selected(), selection.len());
+
+        let order = draw_all(&selection, 5, DrawStrategy::WithReplacement { rounds });
+        assert_eq!(order.len(), rounds);
+        assert!(order.iter().all(|st| selection.contains(st)));
+        // More draws than elements: some are said several times.
+        let distinct: HashSet<_> = order.iter().collect();
+        assert!(distinct.len() < rounds);
+    }
 }
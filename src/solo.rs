@@ -0,0 +1,363 @@
+//! Seven families card game against computer opponents.
+//!
+//! See [`Solo`].
+use rand::rngs::OsRng;
+use yew::prelude::*;
+
+use crate::audio::Playlist;
+use crate::bots::{Bot, BotStrategy};
+use crate::card_game::{Ask, AskResult, CardGame, SetupError, MAX_PLAYERS};
+use crate::family::Family;
+use crate::hot_seat::{ask_text, hand_view, result_text, scores_view, setup_error, winners_text};
+use crate::sentences::Sentence;
+use crate::style;
+
+/// The student, always the first player.
+const STUDENT: usize = 0;
+
+/// Card game component where a student plays alone against [bots][Bot].
+///
+/// The asks of the bots are said aloud and only written once the sounds are done,
+/// so that the student practices listening comprehension.
+pub struct Solo {
+    /// Says the cards asked for.
+    playlist: Playlist,
+    /// Number of bots for the next game.
+    bots: usize,
+    /// Strategy of the bots for the next game.
+    strategy: BotStrategy,
+    /// Game in progress, if any.
+    game: Option<CardGame>,
+    /// Bots of the game in progress, players `1..`.
+    players: Vec<Bot>,
+    /// Why the last game could not be set up.
+    error: Option<SetupError>,
+    /// What is shown to the student.
+    stage: Stage,
+}
+
+/// What is shown to the student, in the order of a turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    /// Choosing the bots.
+    Setup,
+    /// The student looks at their hand and chooses what to ask.
+    Hand {
+        /// Player chosen to be asked, the card comes next.
+        target: Option<usize>,
+    },
+    /// What the last ask gave.
+    Result {
+        ask: Ask,
+        result: AskResult,
+        /// The ask is still being said, the text is hidden until then.
+        listening: bool,
+    },
+    /// All the families are down.
+    Over,
+}
+
+#[derive(Debug)]
+pub enum SoloMsg {
+    /// Change the number of bots for the next game.
+    SetBots(usize),
+    /// Change the strategy of the bots for the next game.
+    SetStrategy(BotStrategy),
+    /// Deal the cards.
+    Start,
+    /// Choose the player to ask.
+    SelectTarget(usize),
+    /// Ask the selected player for a card.
+    Ask(Sentence),
+    /// Show the text of the ask without waiting for the end of the sounds.
+    Reveal,
+    /// Say the last ask again.
+    Replay,
+    /// Go on after the result of an ask.
+    Continue,
+    /// A sound of the playlist just ended.
+    SoundEnded,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct SoloProps {
+    /// Families to play with.
+    pub families: Vec<Family>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for Solo {
+    type Message = SoloMsg;
+    type Properties = SoloProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let playlist = Playlist::new(move |_| link.send_message(SoloMsg::SoundEnded));
+
+        Self {
+            playlist,
+            bots: 1,
+            strategy: BotStrategy::Random,
+            game: None,
+            players: Vec::new(),
+            error: None,
+            stage: Stage::Setup,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match (self.stage, msg) {
+            (Stage::Setup, SoloMsg::SetBots(bots)) => {
+                self.bots = bots.clamp(1, MAX_PLAYERS - 1);
+            }
+            (Stage::Setup, SoloMsg::SetStrategy(strategy)) => self.strategy = strategy,
+            (Stage::Setup, SoloMsg::Start) => {
+                let families = ctx.props().families.iter().copied().collect();
+                match CardGame::new(&families, self.bots + 1, &mut OsRng) {
+                    Ok(game) => {
+                        self.players = (1..=self.bots)
+                            .map(|player| Bot::new(player, self.strategy))
+                            .collect();
+                        self.game = Some(game);
+                        self.error = None;
+                        self.next_turn();
+                    }
+                    Err(err) => self.error = Some(err),
+                }
+            }
+            (Stage::Hand { .. }, SoloMsg::SelectTarget(target)) => {
+                self.stage = Stage::Hand {
+                    target: Some(target),
+                };
+            }
+            (
+                Stage::Hand {
+                    target: Some(target),
+                },
+                SoloMsg::Ask(card),
+            ) => {
+                let ask = Ask {
+                    asker: STUDENT,
+                    target,
+                    card,
+                };
+                if !self.play(ask, false) {
+                    return false;
+                }
+            }
+            (Stage::Result { ask, result, .. }, SoloMsg::Reveal) => {
+                self.stage = Stage::Result {
+                    ask,
+                    result,
+                    listening: false,
+                };
+            }
+            (Stage::Result { ask, .. }, SoloMsg::Replay) => {
+                self.playlist.play(ask.card.sounds());
+                return false;
+            }
+            (Stage::Result { .. }, SoloMsg::Continue) => {
+                self.playlist.stop();
+                self.next_turn();
+            }
+            (stage, SoloMsg::SoundEnded) => {
+                if self.playlist.play_next() {
+                    return false;
+                }
+                match stage {
+                    Stage::Result {
+                        ask,
+                        result,
+                        listening: true,
+                    } => {
+                        self.stage = Stage::Result {
+                            ask,
+                            result,
+                            listening: false,
+                        };
+                    }
+                    _ => return false,
+                }
+            }
+            (_, SoloMsg::Exit) => {
+                self.playlist.stop();
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let exit = html! {
+            <>
+                <hr />
+                <button onclick={ link.callback(|_| SoloMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+            </>
+        };
+
+        let game = match (&self.game, self.stage) {
+            (_, Stage::Setup) | (None, _) => {
+                return html! {
+                    <>
+                        { bots_select(ctx, self.bots) }
+                        { strategy_select(ctx, self.strategy) }
+                        if let Some(err) = self.error {
+                            <p> { setup_error(err) } </p>
+                        }
+                        <button onclick={ link.callback(|_| SoloMsg::Start) }> { "Distribuer les cartes" } </button>
+                        { exit }
+                    </>
+                };
+            }
+            (Some(game), _) => game,
+        };
+
+        let content = match self.stage {
+            Stage::Setup => html! {},
+            Stage::Hand { target } => hand_view(
+                game,
+                player_name,
+                target,
+                link.callback(SoloMsg::SelectTarget),
+                link.callback(SoloMsg::Ask),
+            ),
+            Stage::Result {
+                ask,
+                listening: true,
+                ..
+            } => html! {
+                <>
+                    <p> { format!("Écoutez bien, {} vous parle…", player_name(ask.asker)) } </p>
+                    <button onclick={ link.callback(|_| SoloMsg::Replay) }> { "Répéter la demande" } </button>
+                    <button onclick={ link.callback(|_| SoloMsg::Reveal) }> { "Afficher la demande" } </button>
+                </>
+            },
+            Stage::Result { ask, result, .. } => html! {
+                <>
+                    <p> { ask_text(ask, player_name) } </p>
+                    <p> { result_text(ask, result, player_name, ask.asker == STUDENT) } </p>
+                    <button onclick={ link.callback(|_| SoloMsg::Replay) }> { "Répéter la demande" } </button>
+                    <button onclick={ link.callback(|_| SoloMsg::Continue) }> { "Continuer" } </button>
+                </>
+            },
+            Stage::Over => html! {
+                <>
+                    <p> { "Partie terminée !" } </p>
+                    <p> { winners_text(game, player_name) } </p>
+                </>
+            },
+        };
+
+        html! {
+            <>
+                { content }
+                <hr />
+                { scores_view(game, player_name) }
+                { exit }
+            </>
+        }
+    }
+}
+
+impl Solo {
+    /// Make the ask, say it and let every bot remember it.
+    ///
+    /// Returns `false` if the ask is not allowed.
+    fn play(&mut self, ask: Ask, listening: bool) -> bool {
+        let result = match self.game.as_mut().map(|game| game.ask(ask)) {
+            Some(Ok(result)) => result,
+            _ => return false,
+        };
+
+        for bot in &mut self.players {
+            bot.observe(ask, result);
+        }
+        self.playlist.play(ask.card.sounds());
+        self.stage = Stage::Result {
+            ask,
+            result,
+            listening,
+        };
+
+        true
+    }
+
+    /// Go to the turn of the current player: the hand of the student, or the ask
+    /// of a bot.
+    fn next_turn(&mut self) {
+        let game = match &self.game {
+            Some(game) => game,
+            None => return,
+        };
+
+        if game.is_over() {
+            self.stage = Stage::Over;
+            return;
+        }
+
+        let current = game.current_player();
+        if current == STUDENT {
+            self.stage = Stage::Hand { target: None };
+            return;
+        }
+
+        // The game makes sure the current player always holds a card, and a hand
+        // never holds a whole family, so there is always something to ask for.
+        let ask = self
+            .players
+            .iter()
+            .find(|bot| bot.player() == current)
+            .and_then(|bot| bot.choose(game, &mut OsRng))
+            .expect("a bot can always ask for a card during its turn");
+        self.play(ask, true);
+    }
+}
+
+/// Name of a player, as shown to the student.
+fn player_name(player: usize) -> String {
+    match player {
+        STUDENT => "Vous".to_owned(),
+        bot => format!("Robot {bot}"),
+    }
+}
+
+/// Select the number of bots.
+fn bots_select(ctx: &Context<Solo>, current: usize) -> Html {
+    html! {
+        <>
+            <p> { "Nombre de robots" } </p>
+            { for (1..MAX_PLAYERS).map(|bots| html! {
+                <button
+                    onclick={ ctx.link().callback(move |_| SoloMsg::SetBots(bots)) }
+                    class={ style::button_select_family(bots == current) }
+                >
+                    { bots }
+                </button>
+            }) }
+        </>
+    }
+}
+
+/// Select the strategy of the bots.
+fn strategy_select(ctx: &Context<Solo>, current: BotStrategy) -> Html {
+    html! {
+        <>
+            <p> { "Stratégie des robots" } </p>
+            { for BotStrategy::ALL.into_iter().map(|strategy| html! {
+                <button
+                    onclick={ ctx.link().callback(move |_| SoloMsg::SetStrategy(strategy)) }
+                    class={ style::button_select_family(strategy == current) }
+                >
+                    { strategy.label() }
+                </button>
+            }) }
+        </>
+    }
+}
@@ -0,0 +1,248 @@
+//! Checking a typed word against the label of an element.
+//!
+//! See [`Strictness`] and [`diff()`].
+use yew::prelude::*;
+
+/// How close a typed word must be to the label to be accepted.
+///
+/// Each level accepts everything the previous one does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Same letters, accents and case, only surrounding spaces are ignored.
+    Exact,
+    /// Accents and case are ignored: "ecumoire" for "Écumoire".
+    IgnoreAccentsAndCase,
+    /// Accents, case and hyphens are ignored: "cul de poule" for "Cul-de-poule".
+    IgnoreHyphens,
+}
+
+impl Strictness {
+    /// All the levels, from the strictest.
+    pub const ALL: [Self; 3] = [Self::Exact, Self::IgnoreAccentsAndCase, Self::IgnoreHyphens];
+
+    /// Description shown to players.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Exact => "Orthographe exacte",
+            Self::IgnoreAccentsAndCase => "Accents et majuscules facultatifs",
+            Self::IgnoreHyphens => "Accents, majuscules et tirets facultatifs",
+        }
+    }
+
+    /// What is left of `c` once the ignored differences are removed.
+    fn key(&self, c: char) -> char {
+        match self {
+            Self::Exact => c,
+            Self::IgnoreAccentsAndCase => lowercase(without_accent(c)),
+            Self::IgnoreHyphens => match without_accent(c) {
+                '-' => ' ',
+                c => lowercase(c),
+            },
+        }
+    }
+}
+
+/// A character of the comparison between the expected and the typed words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    /// Typed right, the typed character is kept.
+    Same(char),
+    /// Expected but not typed.
+    Missing(char),
+    /// Typed but not expected.
+    Extra(char),
+}
+
+/// `true` if `given` is accepted for `expected` at the `strictness` level.
+pub fn is_right(expected: &str, given: &str, strictness: Strictness) -> bool {
+    let expected = expected.trim().chars().map(|c| strictness.key(c));
+    let given = given.trim().chars().map(|c| strictness.key(c));
+    expected.eq(given)
+}
+
+/// Character-level difference between `expected` and `given`, characters being
+/// compared at the `strictness` level.
+///
+/// Uses a longest common subsequence so that a single forgotten letter does not
+/// make the rest of the word wrong.
+pub fn diff(expected: &str, given: &str, strictness: Strictness) -> Vec<Change> {
+    let expected: Vec<_> = expected.trim().chars().collect();
+    let given: Vec<_> = given.trim().chars().collect();
+    let same = |i: usize, j: usize| strictness.key(expected[i]) == strictness.key(given[j]);
+
+    // `common[i][j]`: length of the longest common subsequence of
+    // `expected[i..]` and `given[j..]`.
+    let mut common = vec![vec![0usize; given.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..given.len()).rev() {
+            common[i][j] = if same(i, j) {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::with_capacity(expected.len().max(given.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < given.len() {
+        if same(i, j) {
+            changes.push(Change::Same(given[j]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            changes.push(Change::Missing(expected[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Extra(given[j]));
+            j += 1;
+        }
+    }
+    changes.extend(expected[i..].iter().map(|&c| Change::Missing(c)));
+    changes.extend(given[j..].iter().map(|&c| Change::Extra(c)));
+
+    changes
+}
+
+/// The typed word with its mistakes: missing characters underlined in green,
+/// extra ones struck through in red.
+pub fn render_diff(changes: &[Change]) -> Html {
+    html! {
+        <span style="font-family:monospace;font-size:1.5em;">
+            { for changes.iter().map(|change| match *change {
+                Change::Same(c) => html! { <span> { c } </span> },
+                Change::Missing(c) => html! {
+                    <span style="color:green;text-decoration:underline;" title="Lettre manquante"> { c } </span>
+                },
+                Change::Extra(c) => html! {
+                    <span style="color:red;text-decoration:line-through;" title="Lettre en trop"> { c } </span>
+                },
+            }) }
+        </span>
+    }
+}
+
+/// `c` in lowercase, when it is a single character.
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// `c` without its accent, for the accented letters of French.
+fn without_accent(c: char) -> char {
+    match c {
+        'à' | 'â' | 'ä' => 'a',
+        'À' | 'Â' | 'Ä' => 'A',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'î' | 'ï' => 'i',
+        'Î' | 'Ï' => 'I',
+        'ô' | 'ö' => 'o',
+        'Ô' | 'Ö' => 'O',
+        'ù' | 'û' | 'ü' => 'u',
+        'Ù' | 'Û' | 'Ü' => 'U',
+        'ÿ' => 'y',
+        'Ÿ' => 'Y',
+        'ç' => 'c',
+        'Ç' => 'C',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Change::{Extra, Missing, Same};
+
+    #[test]
+    fn exact() {
+        let level = Strictness::Exact;
+        assert!(is_right("Écumoire", "Écumoire", level));
+        assert!(is_right("Écumoire", "  Écumoire ", level));
+        assert!(!is_right("Écumoire", "écumoire", level));
+        assert!(!is_right("Écumoire", "Ecumoire", level));
+        assert!(!is_right("Cul-de-poule", "Cul de poule", level));
+    }
+
+    #[test]
+    fn ignore_accents_and_case() {
+        let level = Strictness::IgnoreAccentsAndCase;
+        assert!(is_right("Écumoire", "ecumoire", level));
+        assert!(is_right("Macédoine", "MACEDOINE", level));
+        assert!(is_right("Chinois étamine", "chinois etamine", level));
+        assert!(is_right("Cul-de-poule", "cul-de-poule", level));
+        assert!(!is_right("Cul-de-poule", "cul de poule", level));
+        assert!(!is_right("Macédoine", "macedoin", level));
+    }
+
+    #[test]
+    fn ignore_hyphens() {
+        let level = Strictness::IgnoreHyphens;
+        assert!(is_right("Cul-de-poule", "cul de poule", level));
+        assert!(is_right("Écumoire", "ecumoire", level));
+        assert!(is_right("Chinois étamine", "Chinois-étamine", level));
+        assert!(!is_right("Cul-de-poule", "culdepoule", level));
+    }
+
+    #[test]
+    fn diff_exact() {
+        assert_eq!(
+            diff("Macédoine", "Macedoine", Strictness::Exact),
+            [
+                Same('M'),
+                Same('a'),
+                Same('c'),
+                Missing('é'),
+                Extra('e'),
+                Same('d'),
+                Same('o'),
+                Same('i'),
+                Same('n'),
+                Same('e'),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_ignore_accents_and_case() {
+        let level = Strictness::IgnoreAccentsAndCase;
+        // Accepted differences are not mistakes, the typed characters are kept.
+        assert_eq!(
+            diff("Écumoire", "ecumoire", level),
+            "ecumoire".chars().map(Same).collect::<Vec<_>>()
+        );
+
+        let mut expected: Vec<_> = "chinois etamin".chars().map(Same).collect();
+        expected.push(Missing('e'));
+        assert_eq!(diff("Chinois étamine", "chinois etamin", level), expected);
+
+        let mut expected: Vec<_> = "cul".chars().map(Same).collect();
+        expected.extend([Missing('-'), Extra(' ')]);
+        expected.extend("de".chars().map(Same));
+        expected.extend([Missing('-'), Extra(' ')]);
+        expected.extend("poule".chars().map(Same));
+        assert_eq!(diff("Cul-de-poule", "cul de poule", level), expected);
+    }
+
+    #[test]
+    fn diff_ignore_hyphens() {
+        let level = Strictness::IgnoreHyphens;
+        assert_eq!(
+            diff("Cul-de-poule", "cul de poule", level),
+            "cul de poule".chars().map(Same).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            diff("Écumoire", "ecumoir", level),
+            [
+                Same('e'),
+                Same('c'),
+                Same('u'),
+                Same('m'),
+                Same('o'),
+                Same('i'),
+                Same('r'),
+                Missing('e'),
+            ]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Values remembered in the local storage of the browser, as JSON.
+//!
+//! Storage is a convenience: when it is not available (private browsing, full
+//! quota, ...), values are silently not saved.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Value saved under `key`, `None` if there is none or if it cannot be read.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    local_storage()
+        .and_then(|storage| storage.get_item(key).ok().flatten())
+        .and_then(|text| serde_json::from_str(&text).ok())
+}
+
+/// Save `value` under `key`.
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    if let (Some(storage), Ok(text)) = (local_storage(), serde_json::to_string(value)) {
+        storage.set_item(key, &text).ok();
+    }
+}
+
+/// Forget the value saved under `key`.
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        storage.remove_item(key).ok();
+    }
+}
+
+/// Local storage of the browser, if available.
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
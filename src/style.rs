@@ -15,3 +15,28 @@ pub fn button_select_family(selected: bool) -> &'static str {
         "family_not_selected"
     }
 }
+
+/// Button showing or hiding the elements of a family.
+pub fn button_expand_family() -> StyleSource<'static> {
+    css!("font-size: 0.8em; margin-bottom: 1%;")
+}
+
+/// Large illustration of the element being called.
+pub fn element_image() -> StyleSource<'static> {
+    css!(
+        display: block;
+        margin: auto;
+        max-height: 40vh;
+        width: auto;
+    )
+}
+
+/// Small illustration of an element in the glossary.
+pub fn glossary_image() -> StyleSource<'static> {
+    css!(
+        display: block;
+        margin: auto;
+        max-height: 20vh;
+        width: auto;
+    )
+}
@@ -0,0 +1,170 @@
+//! "Which family?" listening quiz.
+//!
+//! See [`WhichFamily`].
+use yew::prelude::*;
+
+use crate::audio::Audio;
+use crate::family::Family;
+use crate::score::{Mistake, Score};
+use crate::sentences::{shuffled, Sentence};
+
+/// Quiz component: only the element is said ("je voudrais ..."), the student must
+/// find its family among all the families of the deck.
+pub struct WhichFamily {
+    /// Says the elements.
+    audio: Audio,
+    /// Elements left to ask about, the next one is last.
+    remaining: Vec<Sentence>,
+    /// Answers so far.
+    score: Score,
+    /// What is shown to the student.
+    stage: Stage,
+}
+
+/// What is shown to the student.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+    /// Waiting for a click, browsers only play sounds after one.
+    Start,
+    /// The element was said, waiting for the answer.
+    Question(Sentence),
+    /// The student answered, showing whether it was right.
+    Answered { sentence: Sentence, given: Family },
+    /// All the elements were asked.
+    Over,
+}
+
+#[derive(Debug)]
+pub enum WhichFamilyMsg {
+    /// Go to the next element, or start the quiz.
+    Next,
+    /// Say the element again.
+    Replay,
+    /// The student chose a family.
+    Answer(Family),
+    /// Ask all the elements again, in another order.
+    Restart,
+    /// Go back to the family selection.
+    Exit,
+}
+
+#[derive(Debug, PartialEq, Properties)]
+pub struct WhichFamilyProps {
+    /// Elements to ask about.
+    pub selection: Vec<Sentence>,
+    /// Called to go back to the family selection.
+    pub on_exit: Callback<()>,
+}
+
+impl Component for WhichFamily {
+    type Message = WhichFamilyMsg;
+    type Properties = WhichFamilyProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            audio: Audio::new(|_| ()),
+            remaining: shuffled(&ctx.props().selection),
+            score: Score::default(),
+            stage: Stage::Start,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match (self.stage, msg) {
+            (Stage::Start | Stage::Answered { .. }, WhichFamilyMsg::Next) => {
+                self.stage = match self.remaining.pop() {
+                    Some(sentence) => {
+                        self.audio.play(&sentence.element_sound());
+                        Stage::Question(sentence)
+                    }
+                    None => Stage::Over,
+                };
+            }
+            (Stage::Question(sentence), WhichFamilyMsg::Replay) => {
+                self.audio.play(&sentence.element_sound());
+                return false;
+            }
+            (Stage::Question(sentence), WhichFamilyMsg::Answer(given)) => {
+                self.audio.pause();
+                if given == sentence.family() {
+                    self.score.right(sentence);
+                } else {
+                    self.score.wrong(Mistake {
+                        sentence,
+                        expected: sentence.family().to_string(),
+                        given: given.to_string(),
+                    });
+                }
+                self.stage = Stage::Answered { sentence, given };
+            }
+            (Stage::Over, WhichFamilyMsg::Restart) => {
+                self.remaining = shuffled(&ctx.props().selection);
+                self.score = Score::default();
+                self.stage = Stage::Start;
+            }
+            (_, WhichFamilyMsg::Exit) => {
+                self.audio.pause();
+                ctx.props().on_exit.emit(());
+                return false;
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let content = match self.stage {
+            Stage::Start => html! {
+                <>
+                    <p> { "Écoutez chaque mot et trouvez sa famille." } </p>
+                    <button onclick={ link.callback(|_| WhichFamilyMsg::Next) }> { "Commencer" } </button>
+                </>
+            },
+            Stage::Question(_) => html! {
+                <>
+                    <p> { "De quelle famille est ce mot ?" } </p>
+                    <button onclick={ link.callback(|_| WhichFamilyMsg::Replay) }> { "Réécouter" } </button>
+                    <hr />
+                    { for Family::all().map(|f| {
+                        f.render_button(link.callback(move |_| WhichFamilyMsg::Answer(f)), false)
+                    }) }
+                    <hr />
+                    { self.score.render() }
+                </>
+            },
+            Stage::Answered { sentence, given } => html! {
+                <>
+                    if given == sentence.family() {
+                        <p> { format!("Bonne réponse ! {} est dans la famille {}.", sentence.label(), given) } </p>
+                    } else {
+                        <p> { format!("Non, {} est dans la famille {}.", sentence.label(), sentence.family()) } </p>
+                    }
+                    // The right family is shown selected.
+                    { for Family::all().map(|f| {
+                        f.render_button(Callback::noop(), f == sentence.family())
+                    }) }
+                    <hr />
+                    { self.score.render() }
+                    <button onclick={ link.callback(|_| WhichFamilyMsg::Next) }> { "Mot suivant" } </button>
+                </>
+            },
+            Stage::Over => html! {
+                <>
+                    { self.score.render_recap() }
+                    <button onclick={ link.callback(|_| WhichFamilyMsg::Restart) }> { "Recommencer" } </button>
+                </>
+            },
+        };
+
+        html! {
+            <>
+                { content }
+                <hr />
+                <button onclick={ link.callback(|_| WhichFamilyMsg::Exit) }> { "Retourner à la sélection de familles" } </button>
+            </>
+        }
+    }
+}